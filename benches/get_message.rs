@@ -0,0 +1,46 @@
+//! Counts the allocations made by [`Localiser::get_message()`] for a message with two
+//! placeables, and how long it takes. Run with `cargo bench --bench get_message`.
+
+use std::{alloc::{GlobalAlloc, Layout, System}, sync::atomic::{AtomicUsize, Ordering}, time::Instant};
+use fluent_fluently::{fluent_args, InMemory, Localiser};
+
+/// How many times the message is looked up.
+const ITERATIONS: usize = 1_000_000;
+
+/// Counts the allocations made through the system allocator.
+struct Counting;
+
+/// The number of allocations made so far.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn main() {
+	let source = InMemory::new()
+		.with_resource("en-US", "greeting = Hello, { $name }! You have { $count } new messages.");
+	let loc = Localiser::try_load_from(source, "en-US").unwrap();
+	let args = fluent_args!{ name => "Ada", count => "three" };
+
+	let lookup = || loc.get_message("greeting", "en-US", Some(&args)).unwrap();
+	lookup();
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+	let start = Instant::now();
+	for _ in 0..ITERATIONS {
+		std::hint::black_box(lookup());
+	}
+	let elapsed = start.elapsed();
+	let per_call = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / ITERATIONS as f64;
+	println!("{:?} for {} lookups, {} allocations per call", elapsed, ITERATIONS, per_call);
+}
@@ -0,0 +1,64 @@
+//! Counts the allocations made by looking up a message through [`Localiser::get_message()`]
+//! and [`Localiser::get_message_by_id()`], with the cache enabled so that formatting itself
+//! only allocates the returned [String]. Run with `cargo bench --bench language_id`.
+
+use std::{alloc::{GlobalAlloc, Layout, System}, sync::atomic::{AtomicUsize, Ordering}, time::Instant};
+use fluent_fluently::{InMemory, Localiser, LocaliserBuilder};
+use unic_langid::LanguageIdentifier;
+
+/// How many times each message is looked up.
+const ITERATIONS: usize = 1_000_000;
+
+/// Counts the allocations made through the system allocator.
+struct Counting;
+
+/// The number of allocations made so far.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Loads a small localisation with a few languages.
+fn load() -> Localiser {
+	let source = InMemory::new()
+		.with_resource("en-US", "hello = Hello, world!")
+		.with_resource("it", "hello = Ciao, mondo!")
+		.with_resource("fr", "hello = Bonjour, le monde !")
+		.with_resource("de", "hello = Hallo, Welt!");
+	LocaliserBuilder::with_source(source, "en-US")
+		.cache_static_messages(true)
+		.build()
+		.unwrap()
+}
+
+/// Runs the lookup repeatedly, printing how long it took and how many allocations each call made.
+fn measure(name: &str, lookup: impl Fn() -> String) {
+	lookup();
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+	let start = Instant::now();
+	for _ in 0..ITERATIONS {
+		std::hint::black_box(lookup());
+	}
+	let elapsed = start.elapsed();
+	let per_call = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / ITERATIONS as f64;
+	println!("{}: {:?} for {} lookups, {} allocations per call", name, elapsed, ITERATIONS, per_call);
+}
+
+fn main() {
+	let loc = load();
+	let id = "it".parse::<LanguageIdentifier>().unwrap();
+	measure("baseline", || String::from("Ciao, mondo!"));
+	measure("by string", || loc.get_message("hello", "it", None).unwrap());
+	measure("by identifier", || loc.get_message_by_id("hello", &id, None).unwrap());
+}
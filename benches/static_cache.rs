@@ -0,0 +1,29 @@
+//! Compares looking up a message without arguments with and without
+//! [`LocaliserBuilder::cache_static_messages()`]. Run with `cargo bench --bench static_cache`.
+
+use std::time::Instant;
+use fluent_fluently::{InMemory, Localiser, LocaliserBuilder};
+
+/// How many times each message is looked up.
+const ITERATIONS: u32 = 1_000_000;
+
+/// Loads a small localisation, with or without the cache.
+fn load(cache: bool) -> Localiser {
+	let source = InMemory::new()
+		.with_resource("en-US", "-brand = Fluently\ntitle = Welcome to { -brand }!\nhello = Hello, world!");
+	LocaliserBuilder::with_source(source, "en-US")
+		.cache_static_messages(cache)
+		.build()
+		.unwrap()
+}
+
+fn main() {
+	for cache in [false, true] {
+		let loc = load(cache);
+		let start = Instant::now();
+		for _ in 0..ITERATIONS {
+			std::hint::black_box(loc.get_message(std::hint::black_box("title"), "en-US", None).unwrap());
+		}
+		println!("cache {}: {:?} for {} lookups", if cache { "on" } else { "off" }, start.elapsed(), ITERATIONS);
+	}
+}
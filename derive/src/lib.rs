@@ -0,0 +1,182 @@
+//! # Fluent, fluently - derive
+//! Derive macros for [fluent-fluently](https://github.com/zaaarf/fluent-fluently). You probably
+//! want to use them through its `derive` feature rather than depending on this crate directly.
+
+use std::{collections::BTreeMap, path::{Path, PathBuf}};
+use fluent_syntax::ast;
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse::Parser, parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Fields, LitStr, Token, Type};
+
+/// Derives `fluent_fluently::args::ToFluentArgs` for a struct with named fields.
+/// Each field becomes an argument named after it, and must be [Clone] and convertible into a
+/// `FluentValue`. Fields of type [Option] are only set when they are [Some].
+///
+/// Fields can be customised through the `fluent` attribute:
+///  * `#[fluent(rename = "count")]` sets the argument name to use instead of the field's name;
+///  * `#[fluent(skip)]` leaves the field out entirely.
+#[proc_macro_derive(ToFluentArgs, attributes(fluent))]
+pub fn derive_to_fluent_args(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	match to_fluent_args(input) {
+		Ok(tokens) => tokens.into(),
+		Err(err) => err.to_compile_error().into()
+	}
+}
+
+/// Does the actual work for [`derive_to_fluent_args()`].
+fn to_fluent_args(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+	let fields = match &input.data {
+		Data::Struct(data) => match &data.fields {
+			Fields::Named(fields) => &fields.named,
+			_ => return Err(syn::Error::new_spanned(&input, "ToFluentArgs requires named fields"))
+		},
+		_ => return Err(syn::Error::new_spanned(&input, "ToFluentArgs can only be derived for structs"))
+	};
+
+	let mut setters = Vec::new();
+	for field in fields {
+		let ident = field.ident.as_ref().expect("named fields always have an identifier");
+		let mut name = ident.to_string();
+		let mut skip = false;
+		for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("fluent")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("skip") {
+					skip = true;
+					Ok(())
+				} else if meta.path.is_ident("rename") {
+					name = meta.value()?.parse::<LitStr>()?.value();
+					Ok(())
+				} else {
+					Err(meta.error("unsupported fluent attribute"))
+				}
+			})?;
+		}
+
+		if skip {
+			continue;
+		}
+
+		setters.push(if is_option(&field.ty) {
+			quote! {
+				if let ::std::option::Option::Some(value) = &self.#ident {
+					args.set(#name, ::std::clone::Clone::clone(value));
+				}
+			}
+		} else {
+			quote! {
+				args.set(#name, ::std::clone::Clone::clone(&self.#ident));
+			}
+		});
+	}
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	Ok(quote! {
+		impl #impl_generics ::fluent_fluently::args::ToFluentArgs for #name #ty_generics #where_clause {
+			fn to_args(&self) -> ::fluent_fluently::FluentArgs<'_> {
+				let mut args = ::fluent_fluently::FluentArgs::new();
+				#(#setters)*
+				args
+			}
+		}
+	})
+}
+
+/// Checks whether a type is an [Option], judging by the last segment of its path.
+fn is_option(ty: &Type) -> bool {
+	match ty {
+		Type::Path(path) => path.path.segments.last().is_some_and(|seg| seg.ident == "Option"),
+		_ => false
+	}
+}
+
+/// Generates a `messages` module with a constant for each message defined by the default
+/// language, so that using a message that no longer exists is a compile error. It takes the
+/// path of the locale directory, relative to the crate's manifest, and the code of the default
+/// language, which is read from either `<lang>.ftl` or every `.ftl` file under `<lang>/`.
+///
+/// Each constant is named after the message's identifier in upper snake case, so `hello-world`
+/// becomes `messages::HELLO_WORLD`, and is a `fluent_fluently::key::StaticMessageKey`. Messages
+/// that only have attributes are left out, as they can't be formatted on their own. The crate is
+/// rebuilt whenever one of the files read changes.
+#[proc_macro]
+pub fn messages(input: TokenStream) -> TokenStream {
+	let args = match Punctuated::<LitStr, Token![,]>::parse_terminated.parse(input) {
+		Ok(args) => args,
+		Err(err) => return err.to_compile_error().into()
+	};
+
+	match generate_messages(args.into_iter().collect()) {
+		Ok(tokens) => tokens.into(),
+		Err(err) => err.to_compile_error().into()
+	}
+}
+
+/// Does the actual work for [`messages()`].
+fn generate_messages(args: Vec<LitStr>) -> syn::Result<proc_macro2::TokenStream> {
+	let [dir, language] = args.as_slice() else {
+		return Err(syn::Error::new(proc_macro2::Span::call_site(), "expected the locale directory and the default language"));
+	};
+
+	let root = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join(dir.value());
+	let file = root.join(format!("{}.ftl", language.value()));
+	let mut files = Vec::new();
+	if file.is_file() {
+		files.push(file);
+	} else {
+		collect_ftl_files(&root.join(language.value()), &mut files)
+			.map_err(|err| syn::Error::new_spanned(language, format!("failed to read language {}: {}", language.value(), err)))?;
+		files.sort();
+	}
+
+	let mut keys = BTreeMap::new();
+	for file in &files {
+		let source = std::fs::read_to_string(file)
+			.map_err(|err| syn::Error::new_spanned(dir, format!("failed to read {}: {}", file.display(), err)))?;
+		let resource = fluent_syntax::parser::parse(source.as_str())
+			.map_err(|(_, errors)| syn::Error::new_spanned(dir, format!("failed to parse {}: {:?}", file.display(), errors)))?;
+		for entry in resource.body {
+			if let ast::Entry::Message(ast::Message { id, value: Some(_), .. }) = entry {
+				let name = id.name.replace('-', "_").to_uppercase();
+				if let Some(other) = keys.insert(name.clone(), id.name.to_string()) {
+					if other != id.name {
+						return Err(syn::Error::new_spanned(dir, format!("messages {} and {} would both be named {}", other, id.name, name)));
+					}
+				}
+			}
+		}
+	}
+
+	let consts = keys.iter().map(|(name, key)| {
+		let ident = format_ident!("{}", name);
+		quote! {
+			pub const #ident: ::fluent_fluently::key::StaticMessageKey = ::fluent_fluently::key::StaticMessageKey::__new(#key);
+		}
+	});
+	let tracked = files.iter().map(|file| {
+		let file = file.display().to_string();
+		quote! { const _: &str = include_str!(#file); }
+	});
+
+	Ok(quote! {
+		/// The messages defined by the default language, checked at compile time.
+		pub mod messages {
+			#(#tracked)*
+			#(#consts)*
+		}
+	})
+}
+
+/// Adds every `.ftl` file found in a directory and its subdirectories to the given [Vec].
+fn collect_ftl_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+	for entry in std::fs::read_dir(dir)? {
+		let path = entry?.path();
+		if path.is_dir() {
+			collect_ftl_files(&path, files)?;
+		} else if path.extension().is_some_and(|ext| ext == "ftl") {
+			files.push(path);
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,82 @@
+use std::{borrow::Cow, collections::HashMap, ops::{Deref, DerefMut}};
+use fluent::{FluentArgs, FluentValue};
+
+/// Types that can be turned into a set of [FluentArgs].
+///
+/// With the `derive` feature enabled, this can be derived for structs with named fields: each
+/// field becomes an argument named after it, unless renamed with `#[fluent(rename = "name")]` or
+/// skipped with `#[fluent(skip)]`. Field types must be [Clone] and convertible into a
+/// [FluentValue]; fields of type [Option] are left out when [None].
+pub trait ToFluentArgs {
+	/// Builds the [FluentArgs] representing this value.
+	fn to_args(&self) -> FluentArgs<'_>;
+}
+
+/// Builds a [FluentArgs] out of anything that can be iterated over as key-value pairs, such as
+/// a [HashMap], a [Vec] of tuples or an array.
+///
+/// ```rust
+/// let args = fluent_fluently::args::to_args([("name", "Ada")]);
+/// assert!(args.get("name").is_some());
+/// ```
+pub fn to_args<'a, K, V>(iter: impl IntoIterator<Item = (K, V)>) -> FluentArgs<'a>
+where
+	K: Into<Cow<'a, str>>,
+	V: Into<FluentValue<'a>>
+{
+	iter.into_iter().collect()
+}
+
+/// Thin wrapper around [FluentArgs], needed to provide conversions from standard types.
+/// It dereferences to [FluentArgs], so it can be passed to [`crate::Localiser::get_message()`]
+/// as it is.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use fluent_fluently::{args::Args, FluentArgs, FluentValue};
+///
+/// let map = HashMap::from([("count", FluentValue::from(3))]);
+/// let args = Args::from(map);
+/// let borrowed: Option<&FluentArgs> = Some(&args);
+/// assert!(borrowed.is_some_and(|a| a.get("count").is_some()));
+/// ```
+#[derive(Debug, Default)]
+pub struct Args<'a>(pub FluentArgs<'a>);
+
+impl<'a> Deref for Args<'a> {
+	type Target = FluentArgs<'a>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl DerefMut for Args<'_> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
+}
+
+impl<'a> From<HashMap<&'a str, FluentValue<'a>>> for Args<'a> {
+	fn from(map: HashMap<&'a str, FluentValue<'a>>) -> Self {
+		Self(to_args(map))
+	}
+}
+
+impl<'a> From<HashMap<String, FluentValue<'a>>> for Args<'a> {
+	fn from(map: HashMap<String, FluentValue<'a>>) -> Self {
+		Self(to_args(map))
+	}
+}
+
+impl<'a> From<FluentArgs<'a>> for Args<'a> {
+	fn from(args: FluentArgs<'a>) -> Self {
+		Self(args)
+	}
+}
+
+impl<'a> From<Args<'a>> for FluentArgs<'a> {
+	fn from(args: Args<'a>) -> Self {
+		args.0
+	}
+}
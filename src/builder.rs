@@ -0,0 +1,677 @@
+use std::{borrow::Cow, collections::HashMap, path::PathBuf, result::Result as StdResult, sync::Arc};
+use fluent::FluentValue;
+use fluent_syntax::ast;
+use intl_memoizer::concurrent::IntlLangMemoizer;
+use unic_langid::LanguageIdentifier;
+use crate::{duplicate::{Duplicate, DuplicatePolicy}, encoding::Encoding, error::{Error, LoadFailure, Result}, Localiser, LoadedResource, ResourceSource, TypedFluentBundle};
+
+/// Shorthand for the function type accepted by [`FluentBundle::set_transform()`](fluent::bundle::FluentBundle::set_transform).
+pub type Transform = fn(&str) -> Cow<str>;
+
+/// Shorthand for the function type accepted by [`FluentBundle::set_formatter()`](fluent::bundle::FluentBundle::set_formatter).
+pub type Formatter = fn(&FluentValue, &IntlLangMemoizer) -> Option<String>;
+
+/// Shorthand for the callback accepted by [`LocaliserBuilder::configure_bundle()`].
+pub type BundleConfigurator = Arc<dyn Fn(&mut TypedFluentBundle, &LanguageIdentifier) + Send + Sync>;
+
+/// Settings collected by [LocaliserBuilder], mostly applied to every [TypedFluentBundle] as
+/// soon as it is created.
+#[derive(Clone, Default)]
+pub(crate) struct Options {
+	/// The transform applied to bundles without a language-specific one.
+	transform: Option<Transform>,
+	/// The formatter applied to bundles without a language-specific one.
+	formatter: Option<Formatter>,
+	/// Transforms that only apply to a certain language.
+	language_transforms: HashMap<String, Transform>,
+	/// Formatters that only apply to a certain language.
+	language_formatters: HashMap<String, Formatter>,
+	/// The callback run on every bundle once all other settings are applied.
+	configure_bundle: Option<BundleConfigurator>,
+	/// The locales to create the bundle of a certain language with, in order of preference.
+	language_locales: HashMap<String, Vec<LanguageIdentifier>>,
+	/// Whether to check that all references resolve right after loading.
+	pub(crate) validate_references: bool,
+	/// Whether to check that the arguments passed match the variables used by messages.
+	pub(crate) strict_args: bool,
+	/// What to do with messages and terms defined more than once.
+	duplicate_policy: DuplicatePolicy,
+	/// The encoding files are read in.
+	pub(crate) encoding: Encoding,
+	/// Whether to only read and parse languages other than the default one when first needed.
+	pub(crate) lazy: bool,
+	/// The names of the entries of the source that are added to every language instead of being
+	/// languages themselves.
+	pub(crate) shared: Vec<String>,
+	/// What separates namespaces from message identifiers in keys, if namespaces are enabled.
+	pub(crate) namespace_separator: Option<String>,
+	/// The keys of the messages that were namespaced while loading, shared by every clone.
+	pub(crate) namespaced_ids: Arc<crate::namespace::NamespacedIds>,
+	/// Whether languages other than the default one that fail to load are left out instead of
+	/// making loading fail.
+	pub(crate) lenient_translations: bool,
+	/// The manifest found in the source, filled in while loading.
+	pub(crate) manifest: Option<crate::manifest::Manifest>,
+	/// Whether to keep the formatted values of messages looked up without arguments.
+	pub(crate) cache_static_messages: bool,
+	/// Whether to fail lookups in languages that are not available, rather than falling back on
+	/// the default language.
+	pub(crate) no_default_fallback: bool,
+	/// How the keys of messages read from gettext catalogs are obtained.
+	#[cfg(feature = "gettext")]
+	pub(crate) gettext_keys: crate::gettext::GettextKeys,
+	/// The separator nested keys of JSON files are joined with.
+	#[cfg(feature = "json-import")]
+	pub(crate) json_separator: String,
+	/// Whether language display names fall back on the code when unknown.
+	#[cfg(feature = "display-names")]
+	pub(crate) display_name_fallback: bool
+}
+
+impl Options {
+	/// Creates a new, empty bundle for the given language, with all options applied.
+	pub(crate) fn new_bundle(&self, language: &LanguageIdentifier) -> TypedFluentBundle {
+		let code = language.to_string();
+		let locales = self.language_locales.get(&code)
+			.cloned()
+			.unwrap_or_else(|| vec![language.clone()]);
+		let mut bundle = TypedFluentBundle::new_concurrent(locales);
+		bundle.set_transform(self.language_transforms.get(&code).copied().or(self.transform));
+		bundle.set_formatter(self.language_formatters.get(&code).copied().or(self.formatter));
+		if let Some(configure) = &self.configure_bundle {
+			configure(&mut bundle, language);
+		}
+		bundle
+	}
+
+	/// Whether any formatter was set, for any language.
+	pub(crate) fn has_formatter(&self) -> bool {
+		self.formatter.is_some() || !self.language_formatters.is_empty()
+	}
+
+	/// Creates a bundle for the given language and adds the given resources to it, in order,
+	/// handling duplicate definitions as requested. Duplicates are only recorded if the policy
+	/// is [`DuplicatePolicy::OverrideAndReport`]. With [`DuplicatePolicy::Error`], every resource
+	/// is tried, and all those that could not be added are reported.
+	pub(crate) fn build_bundle(&self, language: &LanguageIdentifier, resources: &[LoadedResource]) -> StdResult<(TypedFluentBundle, Vec<Duplicate>), Vec<LoadFailure>> {
+		let mut bundle = self.new_bundle(language);
+		let mut duplicates = Vec::new();
+		let mut failures = Vec::new();
+		let mut defined: HashMap<String, &PathBuf> = HashMap::new();
+		for res in resources {
+			if self.duplicate_policy == DuplicatePolicy::Error {
+				if let Err(errors) = bundle.add_resource(res.resource.clone()) {
+					failures.push(LoadFailure {
+						language: language.to_string(),
+						path: res.path.clone(),
+						error: Error::FluentError(errors)
+					});
+				}
+				continue;
+			}
+
+			if self.duplicate_policy == DuplicatePolicy::OverrideAndReport {
+				for entry in res.resource.entries() {
+					let key = match entry {
+						ast::Entry::Message(msg) => self.public_key(msg.id.name),
+						ast::Entry::Term(term) => format!("-{}", term.id.name),
+						_ => continue
+					};
+
+					if let Some(shadowed) = defined.insert(key.clone(), &res.path) {
+						duplicates.push(Duplicate {
+							language: language.to_string(),
+							key,
+							winning_file: res.path.clone(),
+							shadowed_file: shadowed.clone()
+						});
+					}
+				}
+			}
+
+			bundle.add_resource_overriding(res.resource.clone());
+		}
+
+		if failures.is_empty() {
+			Ok((bundle, duplicates))
+		} else {
+			Err(failures)
+		}
+	}
+
+	/// Validates and normalises the language codes used as filters.
+	fn normalise(self) -> Result<Self> {
+		fn normalise_keys<T>(map: HashMap<String, T>) -> Result<HashMap<String, T>> {
+			map.into_iter()
+				.map(|(lang, value)| Ok((crate::fallback::parse_code(&lang)?.0, value)))
+				.collect()
+		}
+
+		if let Some(separator) = &self.namespace_separator {
+			if separator.is_empty() || separator.contains(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') {
+				return Err(Error::GenericError(format!("{} cannot be used as a namespace separator!", separator)));
+			}
+		}
+
+		Ok(Self {
+			language_transforms: normalise_keys(self.language_transforms)?,
+			language_formatters: normalise_keys(self.language_formatters)?,
+			language_locales: normalise_keys(self.language_locales)?,
+			..self
+		})
+	}
+}
+
+/// A builder for [Localiser], for when the defaults used by [`Localiser::try_load()`] are not
+/// enough. You can obtain a new instance by calling [`Localiser::builder()`].
+pub struct LocaliserBuilder {
+	/// The source to load the localisation files from.
+	source: Arc<dyn ResourceSource>,
+	/// The identifier of the default language.
+	default_language: String,
+	/// The settings to apply to each bundle.
+	options: Options,
+	/// The locale lists requested through [`Self::bundle_locales()`], not yet validated.
+	bundle_locales: HashMap<String, Vec<String>>,
+	/// The directory to read from, if the builder was created from a path, replacing the source.
+	#[cfg(feature = "fs")]
+	file_system: Option<crate::FileSystem>
+}
+
+impl LocaliserBuilder {
+	/// Creates a new builder with the given path and default language.
+	#[cfg(feature = "fs")]
+	pub fn new(path: impl AsRef<std::path::Path>, default_language: &str) -> Self {
+		let file_system = crate::FileSystem::new(path.as_ref());
+		Self {
+			file_system: Some(file_system.clone()),
+			..Self::with_source(file_system, default_language)
+		}
+	}
+
+	/// Creates a new builder reading from the given [ResourceSource], with the given default
+	/// language.
+	pub fn with_source(source: impl ResourceSource + 'static, default_language: &str) -> Self {
+		Self {
+			source: Arc::new(source),
+			default_language: default_language.to_string(),
+			options: Options {
+				#[cfg(feature = "json-import")]
+				json_separator: "-".to_string(),
+				..Options::default()
+			},
+			bundle_locales: HashMap::new(),
+			#[cfg(feature = "fs")]
+			file_system: None
+		}
+	}
+
+	/// Sets a function that every piece of literal text will be passed through while formatting
+	/// messages. Arguments are left untouched. A transform set for a specific language through
+	/// [`Self::transform_for()`] takes precedence over this.
+	pub fn transform(mut self, transform: Transform) -> Self {
+		self.options.transform = Some(transform);
+		self
+	}
+
+	/// Like [`Self::transform()`], but only applies to the given language.
+	pub fn transform_for(mut self, language: &str, transform: Transform) -> Self {
+		self.options.language_transforms.insert(language.to_string(), transform);
+		self
+	}
+
+	/// Sets a function used to format the [FluentValue]s passed as arguments. Returning [None]
+	/// from it makes Fluent use its own formatting. A formatter set for a specific language
+	/// through [`Self::formatter_for()`] takes precedence over this.
+	pub fn formatter(mut self, formatter: Formatter) -> Self {
+		self.options.formatter = Some(formatter);
+		self
+	}
+
+	/// Like [`Self::formatter()`], but only applies to the given language.
+	pub fn formatter_for(mut self, language: &str, formatter: Formatter) -> Self {
+		self.options.language_formatters.insert(language.to_string(), formatter);
+		self
+	}
+
+	/// Sets a callback to run on every bundle as soon as it is created, for settings that have no
+	/// dedicated method here, such as custom functions. It runs after transforms, formatters and
+	/// locales are applied, so it may override them, and before any resource is added, so
+	/// resources may use whatever it registers. It also runs for languages loaded lazily or added
+	/// later. Setting another callback replaces the previous one.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, LocaliserBuilder};
+	///
+	/// let source = InMemory::new().with_resource("en-US", "greeting = Hello, { $name }!");
+	/// let loc = LocaliserBuilder::with_source(source, "en-US")
+	///     .configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+	///     .build()
+	///     .unwrap();
+	/// let mut args = fluent_fluently::FluentArgs::new();
+	/// args.set("name", "Ada");
+	/// assert_eq!(loc.get_message("greeting", "en-US", Some(&args)).unwrap(), "Hello, Ada!");
+	/// ```
+	pub fn configure_bundle(mut self, configure: impl Fn(&mut TypedFluentBundle, &LanguageIdentifier) + Send + Sync + 'static) -> Self {
+		self.options.configure_bundle = Some(Arc::new(configure));
+		self
+	}
+
+	/// Sets the list of locales the bundle for the given language is created with, in order of
+	/// preference. By default, a bundle only uses the locale it is named after. This matters for
+	/// things like plural rules: a private-use language such as `qaa` has no CLDR data of its own,
+	/// but can be told to behave like `pl`. Fluent takes plural rules from the first locale in the
+	/// list, falling back on English ones if it has none, so the locale to behave like must come
+	/// first; later ones are not looked at. Rules are found by language, so a pseudo-locale such
+	/// as `en-XA` or `pl-XA` already gets those of `en` or `pl` without any configuration.
+	pub fn bundle_locales(mut self, language: &str, locales: &[&str]) -> Self {
+		self.bundle_locales.insert(
+			language.to_string(),
+			locales.iter().map(|l| l.to_string()).collect()
+		);
+		self
+	}
+
+	/// Makes loading fail with [`Error::BrokenReferences`](crate::error::Error::BrokenReferences)
+	/// if any message or term references something that is not defined in the same language.
+	/// See [`Localiser::validate_references()`].
+	pub fn validate_references(mut self, validate: bool) -> Self {
+		self.options.validate_references = validate;
+		self
+	}
+
+	/// Makes message lookups fail with
+	/// [`Error::ArgumentMismatch`](crate::error::Error::ArgumentMismatch) if the arguments passed
+	/// don't match the variables used by the message. A variable counts as used if it appears
+	/// anywhere in the message, even in a variant of a select expression that was not picked.
+	pub fn strict_args(mut self, strict: bool) -> Self {
+		self.options.strict_args = strict;
+		self
+	}
+
+	/// Sets what to do when a message or term is defined more than once within the same language.
+	/// See [DuplicatePolicy].
+	pub fn on_duplicate(mut self, policy: DuplicatePolicy) -> Self {
+		self.options.duplicate_policy = policy;
+		self
+	}
+
+	/// Makes loading go on without the languages other than the default one that have files that
+	/// can't be read, parsed or added to their bundle, instead of failing with
+	/// [`Error::LoadErrors`]. Requests for those languages then fall back like for any other
+	/// missing language, and [`Localiser::load_failures()`] tells what went wrong. Failures in
+	/// the default language, or in resources shared through [`Self::shared()`], still make
+	/// loading fail; the error then lists every failure found, including those that would have
+	/// been tolerated.
+	///
+	/// ```rust
+	/// use fluent_fluently::{error::Error, InMemory, LocaliserBuilder};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("pt-BR", "hello = { Olá!");
+	/// let loc = LocaliserBuilder::with_source(source, "en-US").lenient_translations(true).build().unwrap();
+	/// assert_eq!(loc.get_message("hello", "pt-BR", None).unwrap(), "Hello!");
+	/// assert_eq!(loc.load_failures()[0].language, "pt-BR");
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = { Hello!")
+	///     .with_resource("pt-BR", "hello = { Olá!");
+	/// let err = LocaliserBuilder::with_source(source, "en-US").lenient_translations(true).build().unwrap_err();
+	/// assert!(matches!(err, Error::LoadErrors(errors) if errors.failures.len() == 2));
+	/// ```
+	pub fn lenient_translations(mut self, lenient: bool) -> Self {
+		self.options.lenient_translations = lenient;
+		self
+	}
+
+	/// Makes the entry of the source with the given name, such as a `_shared.ftl` file or a
+	/// `shared` directory with [FileSystem](crate::FileSystem), hold resources shared by every
+	/// language rather than be a language itself. Shared resources are read and parsed once, and
+	/// added to the bundle of each language before its own, so that languages can override
+	/// what they define as allowed by [`Self::on_duplicate()`]. They also come first in
+	/// languages added later, such as through [`Localiser::insert_from_reader()`]. Can be called
+	/// more than once to share several entries, which are added in the order the source lists
+	/// them.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, LocaliserBuilder};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("_shared", "-brand-name = Fluently")
+	///     .with_resource("en-US", "welcome = Welcome to { -brand-name }!")
+	///     .with_resource("it", "welcome = Benvenuti in { -brand-name }!");
+	/// let loc = LocaliserBuilder::with_source(source, "en-US").shared("_shared").build().unwrap();
+	/// assert_eq!(loc.get_message("welcome", "en-US", None).unwrap(), "Welcome to Fluently!");
+	/// assert_eq!(loc.get_message("welcome", "it", None).unwrap(), "Benvenuti in Fluently!");
+	/// ```
+	pub fn shared(mut self, name: &str) -> Self {
+		self.options.shared.push(name.to_string());
+		self
+	}
+
+	/// Makes the path of each file within its language directory part of the keys of the messages
+	/// it defines, so that the same identifier can be used in different files. With `/` as the
+	/// separator, the `not-found` message in `en-US/errors.ftl` is looked up as
+	/// `errors/not-found`, and the `subject` message in `en-US/emails/welcome.ftl` as
+	/// `emails/welcome/subject`; attributes still follow a `.`. Languages made of a single file
+	/// are not namespaced. The separator may not contain characters allowed in Fluent
+	/// identifiers, nor `.`.
+	///
+	/// References to other messages are resolved within the same file. Terms are not namespaced,
+	/// so they can still be shared. Keys are reported in their namespaced form by everything that
+	/// lists them, such as [`Localiser::message_keys()`] and [`Localiser::check_coverage()`];
+	/// only the bundles themselves know messages by the identifiers actually given to Fluent,
+	/// where the separator is replaced by `__`. Namespaces come from
+	/// [`ResourceSource::namespace()`](crate::source::ResourceSource::namespace), so sources
+	/// other than [FileSystem](crate::FileSystem) may not support them.
+	///
+	/// ```rust
+	/// # #[cfg(feature = "fs")] {
+	/// use fluent_fluently::{FileSystem, LocaliserBuilder};
+	///
+	/// let root = std::env::temp_dir().join("fluent-fluently-namespaced");
+	/// std::fs::create_dir_all(root.join("en-US").join("emails")).unwrap();
+	/// std::fs::write(root.join("en-US").join("errors.ftl"), "title = Error\nnot-found = { title }: not found").unwrap();
+	/// std::fs::write(root.join("en-US").join("menu.ftl"), "title = Menu").unwrap();
+	/// std::fs::write(root.join("en-US").join("emails").join("welcome.ftl"), "subject = Welcome!").unwrap();
+	///
+	/// let loc = LocaliserBuilder::with_source(FileSystem::new(&root), "en-US").namespaced("/").build().unwrap();
+	/// assert_eq!(loc.get_message("errors/not-found", "en-US", None).unwrap(), "Error: not found");
+	/// assert_eq!(loc.get_message("menu/title", "en-US", None).unwrap(), "Menu");
+	/// assert_eq!(loc.get_message("emails/welcome/subject", "en-US", None).unwrap(), "Welcome!");
+	/// assert_eq!(loc.message_keys("en-US").unwrap(), ["emails/welcome/subject", "errors/title", "errors/not-found", "menu/title"]);
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// # }
+	/// ```
+	pub fn namespaced(mut self, separator: &str) -> Self {
+		self.options.namespace_separator = Some(separator.to_string());
+		self
+	}
+
+	/// Sets the encoding files are read in, which is strict UTF-8 by default. See [Encoding].
+	///
+	/// ```rust
+	/// # #[cfg(feature = "fs")] {
+	/// use fluent_fluently::{Encoding, FileSystem, LocaliserBuilder};
+	///
+	/// let root = std::env::temp_dir().join("fluent-fluently-encoding");
+	/// std::fs::create_dir_all(&root).unwrap();
+	/// std::fs::write(root.join("it.ftl"), b"coffee = caff\xe8").unwrap();
+	///
+	/// assert!(LocaliserBuilder::with_source(FileSystem::new(&root), "it").build().is_err());
+	/// let loc = LocaliserBuilder::with_source(FileSystem::new(&root), "it").encoding(Encoding::Auto).build().unwrap();
+	/// assert_eq!(loc.get_message("coffee", "it", None).unwrap(), "caffè");
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// # }
+	/// ```
+	pub fn encoding(mut self, encoding: Encoding) -> Self {
+		self.options.encoding = encoding;
+		self
+	}
+
+	/// Makes [`Localiser::get_message()`] keep the formatted value of each message it is asked
+	/// for without arguments, so that later lookups of the same message in the same language
+	/// skip formatting. Calls passing arguments never use the cache. The cache is safe to use
+	/// from multiple threads, is shared with clones until either changes its messages, and is
+	/// emptied whenever the loaded messages change, such as through
+	/// [`Localiser::insert_from_reader()`].
+	pub fn cache_static_messages(mut self, cache: bool) -> Self {
+		self.options.cache_static_messages = cache;
+		self
+	}
+
+	/// Sets how the keys of messages read from gettext `.po` files are obtained from their
+	/// `msgid`s. See [`gettext::po_to_ftl()`](crate::gettext::po_to_ftl).
+	#[cfg(feature = "gettext")]
+	pub fn gettext_keys(mut self, keys: crate::gettext::GettextKeys) -> Self {
+		self.options.gettext_keys = keys;
+		self
+	}
+
+	/// Sets the separator the nested keys of JSON files are joined with, which is `-` by default.
+	/// See [`json::json_to_ftl()`](crate::json::json_to_ftl).
+	#[cfg(feature = "json-import")]
+	pub fn json_separator(mut self, separator: &str) -> Self {
+		self.options.json_separator = separator.to_string();
+		self
+	}
+
+	/// Sets whether lookups in a language that is not available fall back on the default
+	/// language, which is the case by default. When disabled, they fail with
+	/// [`Error::MissingLanguageError`](crate::error::Error::MissingLanguageError) instead, so
+	/// that a missing translation is reported as such. Less specific versions of the requested
+	/// language are still tried first, as described in [`Localiser::resolve_language()`]: `it-IT`
+	/// is still served by `it`, but never by the default language.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, LocaliserBuilder};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!");
+	/// let loc = LocaliserBuilder::with_source(source, "en-US").fallback_to_default(false).build().unwrap();
+	/// assert_eq!(loc.get_message("hello", "it-IT", None).unwrap(), "Ciao!");
+	/// assert!(loc.get_message("hello", "de", None).is_err());
+	/// ```
+	pub fn fallback_to_default(mut self, fallback: bool) -> Self {
+		self.options.no_default_fallback = !fallback;
+		self
+	}
+
+	/// Makes [`Localiser::language_display_name()`] return the code it was given, rather than
+	/// [None], when it has no name for it.
+	#[cfg(feature = "display-names")]
+	pub fn display_name_fallback(mut self, fallback: bool) -> Self {
+		self.options.display_name_fallback = fallback;
+		self
+	}
+
+	/// Makes loading only list the available languages, deferring reading and parsing their
+	/// resources until a language is first used. The default language is always loaded right
+	/// away. See [`Localiser::preload()`] for the details.
+	pub fn lazy(mut self, lazy: bool) -> Self {
+		self.options.lazy = lazy;
+		self
+	}
+
+	/// Sets whether files and directories whose name starts with `.`, such as `.DS_Store` or
+	/// `.git`, are considered, which is not the case by default. Only applies to builders
+	/// created from a path, through [`Self::new()`] or [`Localiser::builder()`]: for those created
+	/// through [`Self::with_source()`], see [`FileSystem::include_hidden()`](crate::FileSystem::include_hidden).
+	#[cfg(feature = "fs")]
+	pub fn include_hidden(mut self, include: bool) -> Self {
+		self.file_system = self.file_system.map(|file_system| file_system.include_hidden(include));
+		self
+	}
+
+	/// Only loads files whose path relative to the root matches the given pattern, or any of the
+	/// patterns given through other calls. Like [`Self::include_hidden()`], this only applies to
+	/// builders created from a path. See [`FileSystem::include_glob()`](crate::FileSystem::include_glob)
+	/// for the syntax.
+	///
+	/// ```rust
+	/// # #[cfg(feature = "fs")] {
+	/// use fluent_fluently::Localiser;
+	///
+	/// let root = std::env::temp_dir().join("fluent-fluently-builder-include-glob");
+	/// std::fs::create_dir_all(root.join("en-US")).unwrap();
+	/// std::fs::write(root.join("en-US").join("menu.ftl"), "save = Save").unwrap();
+	/// std::fs::write(root.join("en-US").join("menu.draft.ftl"), "open = Open").unwrap();
+	///
+	/// let loc = Localiser::builder(&root, "en-US").include_glob("**/*.draft.ftl").build().unwrap();
+	/// assert_eq!(loc.message_keys("en-US").unwrap(), ["open"]);
+	/// assert_eq!(loc.excluded_resources(), [root.join("en-US").join("menu.ftl")]);
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// # }
+	/// ```
+	#[cfg(feature = "fs")]
+	pub fn include_glob(mut self, pattern: &str) -> Self {
+		self.file_system = self.file_system.map(|file_system| file_system.include_glob(pattern));
+		self
+	}
+
+	/// Skips files whose path relative to the root matches the given pattern, or any of the
+	/// patterns given through other calls, even if they match those given to
+	/// [`Self::include_glob()`]. Like [`Self::include_hidden()`], this only applies to builders
+	/// created from a path. The files skipped are listed by
+	/// [`Localiser::excluded_resources()`].
+	#[cfg(feature = "fs")]
+	pub fn exclude_glob(mut self, pattern: &str) -> Self {
+		self.file_system = self.file_system.map(|file_system| file_system.exclude_glob(pattern));
+		self
+	}
+
+	/// Tries to build the [Localiser], loading the files as described in [`Localiser::try_load()`].
+	pub fn build(mut self) -> Result<Localiser> {
+		#[cfg(feature = "fs")]
+		if let Some(file_system) = self.file_system {
+			self.source = Arc::new(file_system);
+		}
+
+		for (language, locales) in self.bundle_locales {
+			let locales = locales.iter()
+				.map(|l| l.parse::<LanguageIdentifier>())
+				.collect::<std::result::Result<Vec<_>, _>>()?;
+			self.options.language_locales.insert(language, locales);
+		}
+
+		Localiser::load(self.source, &self.default_language, self.options.normalise()?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::borrow::Cow;
+	use crate::{fluent_args, FluentValue, InMemory, Localiser, LocaliserBuilder};
+
+	fn builder() -> LocaliserBuilder {
+		LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", "hello = Hello, { $name }!\ncount = { $n } items")
+			.with_resource("it", "hello = Ciao, { $name }!"), "en-US")
+			.configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+	}
+
+	fn shout(text: &str) -> Cow<'_, str> {
+		Cow::Owned(text.to_uppercase())
+	}
+
+	fn whisper(text: &str) -> Cow<'_, str> {
+		Cow::Owned(text.to_lowercase())
+	}
+
+	#[test]
+	fn transforms_only_literal_text() {
+		let loc = builder().transform(shout).build().unwrap();
+		let args = fluent_args!{ name => "Anna" };
+		assert_eq!(loc.get_message("hello", "en-US", Some(&args)).unwrap(), "HELLO, Anna!");
+		assert_eq!(loc.get_message("hello", "it", Some(&args)).unwrap(), "CIAO, Anna!");
+	}
+
+	#[test]
+	fn transforms_by_language() {
+		let mut loc = builder()
+			.transform(shout)
+			.transform_for("it", whisper)
+			.build().unwrap();
+		let args = fluent_args!{ name => "Anna" };
+		assert_eq!(loc.get_message("hello", "en-US", Some(&args)).unwrap(), "HELLO, Anna!");
+		assert_eq!(loc.get_message("hello", "it", Some(&args)).unwrap(), "ciao, Anna!");
+
+		// languages added at runtime use the same settings
+		loc.insert_from_reader("fr", std::io::Cursor::new("hello = Bonjour, { $name } !")).unwrap();
+		assert_eq!(loc.get_message("hello", "fr", Some(&args)).unwrap(), "BONJOUR, Anna !");
+	}
+
+	#[test]
+	fn uses_plural_rules_of_bundle_locales() {
+		let source = InMemory::new()
+			.with_resource("en-US", "apples = { $n ->\n    [one] one\n    [few] few\n    [many] many\n   *[other] other\n}")
+			.with_resource("qaa", "apples = { $n ->\n    [one] one\n    [few] few\n    [many] many\n   *[other] other\n}");
+		let apples = |loc: &Localiser| [1, 3, 5].map(|n| loc.get_message("apples", "qaa", Some(&fluent_args!{ n => n })).unwrap());
+
+		let loc = LocaliserBuilder::with_source(source.clone(), "en-US").build().unwrap();
+		assert_eq!(apples(&loc), ["one", "other", "other"]);
+		let loc = LocaliserBuilder::with_source(source, "en-US")
+			.bundle_locales("qaa", &["pl", "qaa"])
+			.build().unwrap();
+		assert_eq!(apples(&loc), ["one", "few", "many"]);
+		assert_eq!(loc.get_message("apples", "en-US", Some(&fluent_args!{ n => 3 })).unwrap(), "other");
+	}
+
+	#[test]
+	fn only_first_bundle_locale_gives_plural_rules() {
+		let apples = "apples = { $n ->\n    [one] one\n    [few] few\n    [many] many\n   *[other] other\n}";
+		let loc = LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", apples)
+			.with_resource("en-XA", apples)
+			.with_resource("pl-XA", apples)
+			.with_resource("qaa", apples), "en-US")
+			.bundle_locales("en-XA", &["en-XA", "en-US"])
+			.bundle_locales("qaa", &["qaa", "pl"])
+			.build().unwrap();
+		let plurals = |language: &str| [0, 1, 3, 5].map(|n| loc.get_message("apples", language, Some(&fluent_args!{ n => n })).unwrap());
+
+		assert_eq!(plurals("en-XA"), ["other", "one", "other", "other"]);
+		assert_eq!(plurals("pl-XA"), ["many", "one", "few", "many"]);
+		// pl comes second, so English rules are used
+		assert_eq!(plurals("qaa"), ["other", "one", "other", "other"]);
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn includes_hidden_files() {
+		let root = std::env::temp_dir().join("fluent-fluently-builder-hidden");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join("it")).unwrap();
+		std::fs::write(root.join(".DS_Store"), [0, 0, 0, 1, 0xff]).unwrap();
+		std::fs::write(root.join("en-US.ftl"), "hello = Hello!").unwrap();
+		std::fs::write(root.join("it").join("main.ftl"), "hello = Ciao!").unwrap();
+		std::fs::write(root.join("it").join(".main.ftl.swp"), [0, 5, 22, 7, 0xff]).unwrap();
+		std::fs::write(root.join("it").join("._main.ftl"), [0, 5, 22, 7, 0xff]).unwrap();
+
+		let loc = Localiser::builder(&root, "en-US").build().unwrap();
+		assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+		assert_eq!(Localiser::builder(&root, "en-US").include_hidden(false).build().unwrap().available_languages().count(), 2);
+		assert!(Localiser::builder(&root, "en-US").include_hidden(true).build().is_err());
+		std::fs::remove_dir_all(root).unwrap();
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn excludes_globs() {
+		let root = std::env::temp_dir().join("fluent-fluently-builder-exclude-glob");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join("en-US").join("mt")).unwrap();
+		std::fs::write(root.join("en-US").join("menu.ftl"), "save = Save").unwrap();
+		std::fs::write(root.join("en-US").join("menu.draft.ftl"), "open = Open").unwrap();
+		std::fs::write(root.join("en-US").join("mt").join("menu.ftl"), "close = Close").unwrap();
+		std::fs::write(root.join("it.draft.ftl"), "save = Salva").unwrap();
+
+		let loc = Localiser::builder(&root, "en-US")
+			.exclude_glob("**/*.draft.ftl")
+			.exclude_glob("**/mt/**")
+			.build().unwrap();
+		assert_eq!(loc.message_keys("en-US").unwrap(), ["save"]);
+		assert_eq!(loc.excluded_resources(), [
+			root.join("en-US").join("menu.draft.ftl"),
+			root.join("en-US").join("mt").join("menu.ftl"),
+			root.join("it.draft.ftl")
+		]);
+		assert!(Localiser::try_load(&root, "en-US").unwrap().excluded_resources().is_empty());
+		std::fs::remove_dir_all(root).unwrap();
+	}
+
+	#[test]
+	fn formats_arguments() {
+		let loc = builder()
+			.formatter(|value, _| match value {
+				FluentValue::Number(number) => Some(format!("#{}", number.value)),
+				_ => None
+			})
+			.transform(shout)
+			.build().unwrap();
+		assert_eq!(loc.get_message("count", "en-US", Some(&fluent_args!{ n => 3 })).unwrap(), "#3 ITEMS");
+		assert_eq!(loc.get_message("hello", "en-US", Some(&fluent_args!{ name => "Anna" })).unwrap(), "HELLO, Anna!");
+	}
+}
@@ -0,0 +1,42 @@
+use std::{collections::HashMap, sync::{Arc, RwLock}};
+use crate::Localiser;
+
+/// The formatted values of messages that take no arguments, by language and key.
+/// See [`LocaliserBuilder::cache_static_messages()`](crate::LocaliserBuilder::cache_static_messages).
+#[derive(Default)]
+pub(crate) struct MessageCache {
+	/// The cached values, by the language of the bundle they came from and then by message key.
+	entries: RwLock<HashMap<String, HashMap<String, Arc<str>>>>
+}
+
+impl MessageCache {
+	/// Gets the cached value of a message, if there is one.
+	pub(crate) fn get(&self, language: &str, key: &str) -> Option<Arc<str>> {
+		// a map insertion can't leave things half-done, so a poisoned lock is still fine to use
+		self.entries.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.get(language)
+			.and_then(|messages| messages.get(key))
+			.cloned()
+	}
+
+	/// Stores the value of a message.
+	pub(crate) fn insert(&self, language: &str, key: &str, value: Arc<str>) {
+		self.entries.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.entry(language.to_string())
+			.or_default()
+			.insert(key.to_string(), value);
+	}
+}
+
+impl Localiser {
+	/// Empties the cache of messages without arguments, if it was enabled through
+	/// [`LocaliserBuilder::cache_static_messages()`](crate::LocaliserBuilder::cache_static_messages).
+	/// Every method that changes the loaded messages already does this, so it is only useful to
+	/// free the memory it takes. Clones share the same cache until one of them clears it, which
+	/// only gives that one a new, empty cache: the others keep using the old one.
+	pub fn clear_message_cache(&mut self) {
+		self.cache = Arc::default();
+	}
+}
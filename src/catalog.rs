@@ -0,0 +1,332 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::{convert::{is_identifier, push_pattern, Piece}, error::{Error, Result}, export::pattern_to_ftl, InMemory, Localiser};
+
+/// What the texts in a [Catalog] should be.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CatalogMode {
+	/// The raw FTL source of each pattern, for clients that format messages themselves.
+	#[default]
+	Source,
+	/// Each pattern formatted with no arguments. Placeables referring to arguments are left as
+	/// Fluent renders them when these are missing, such as `{$name}`.
+	Formatted
+}
+
+/// All the texts of a single language, keyed by message identifier, or by `message.attribute`
+/// for attributes. With the `serde` feature enabled, it can be serialised and deserialised.
+/// You can obtain a new instance by calling [`Localiser::catalog()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Catalog {
+	/// The language the texts are in.
+	pub language: String,
+	/// A [HashMap] tying each key to its text.
+	pub messages: HashMap<String, String>
+}
+
+impl Localiser {
+	/// Collects all the texts of a language into a [Catalog]. The language is resolved as
+	/// described in [`Self::resolve_language()`].
+	pub fn catalog(&self, language: &str, mode: CatalogMode) -> Result<Catalog> {
+		let language = self.try_resolve_language(language)?;
+		let bundle = self.loaded_bundle(language)?;
+		let text = |pattern| match mode {
+			CatalogMode::Source => pattern_to_ftl(pattern),
+			CatalogMode::Formatted => bundle.format_pattern(pattern, None, &mut Vec::new()).to_string()
+		};
+
+		let mut messages = HashMap::new();
+		for msg in self.message_entries(language) {
+			if let Some(value) = &msg.value {
+				messages.insert(self.public_key(msg.id.name), text(value));
+			}
+			for attr in &msg.attributes {
+				messages.insert(format!("{}.{}", self.public_key(msg.id.name), attr.id.name), text(&attr.value));
+			}
+		}
+
+		Ok(Catalog {
+			language: language.to_string(),
+			messages
+		})
+	}
+}
+
+/// The texts of every language, formatted with no arguments, keyed by language code and then by
+/// message identifier, `message.attribute` for attributes, or `-term` for terms. With the `serde`
+/// feature enabled, it can be serialised and deserialised as `{ language: { key: text } }`, for
+/// instance to be sent to a translation management system. You can obtain a new instance by
+/// calling [`Localiser::export_data()`], and turn one back into a [Localiser] through
+/// [`Localiser::from_export_data()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct ExportData {
+	/// A [BTreeMap] tying each language code to the texts in that language, so that the order
+	/// is the same on every run.
+	pub languages: BTreeMap<String, BTreeMap<String, String>>
+}
+
+impl ExportData {
+	/// Turns the texts of a single language back into FTL source text, failing if any key is
+	/// not made of valid Fluent identifiers.
+	fn to_ftl(texts: &BTreeMap<String, String>) -> Result<String> {
+		let mut entries: BTreeMap<&str, Vec<(Option<&str>, &str)>> = BTreeMap::new();
+		for (key, text) in texts {
+			let (id, attribute) = match key.split_once('.') {
+				Some((id, attribute)) => (id, Some(attribute)),
+				None => (key.as_str(), None)
+			};
+			if !is_identifier(id.strip_prefix('-').unwrap_or(id)) || !attribute.is_none_or(is_identifier) {
+				return Err(Error::GenericError(format!("{} is not a valid key!", key)));
+			}
+
+			entries.entry(id).or_default().push((attribute, text));
+		}
+
+		let mut ftl = String::new();
+		let push_text = |ftl: &mut String, text: &str| match text {
+			"" => ftl.push_str(" { \"\" }"),
+			text => push_pattern(ftl, &[Piece::Text(text)], "    ")
+		};
+		for (id, texts) in entries {
+			ftl.push_str(id);
+			ftl.push_str(" =");
+			// keys without attribute sort first, so the value comes before the attributes
+			for (attribute, text) in texts {
+				if let Some(attribute) = attribute {
+					ftl.push_str("\n    .");
+					ftl.push_str(attribute);
+					ftl.push_str(" =");
+				}
+				push_text(&mut ftl, text);
+			}
+			ftl.push('\n');
+		}
+
+		Ok(ftl)
+	}
+}
+
+impl Localiser {
+	/// Collects the texts of every message, attribute and term of every language, formatted
+	/// with no arguments, into an [ExportData]. Placeables referring to arguments are left as
+	/// Fluent renders them when these are missing, such as `{$name}`. Languages loaded lazily
+	/// are loaded.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "-brand = Fluently\nhello = Hello!\nlogin = Log in\n    .title = Log into { -brand }")
+	///     .with_resource("it", "hello = Ciao!"), "en-US").unwrap();
+	/// let data = loc.export_data().unwrap();
+	/// assert_eq!(data.languages["en-US"]["login.title"], "Log into Fluently");
+	/// assert_eq!(data.languages["en-US"]["-brand"], "Fluently");
+	/// assert_eq!(data.languages["it"]["hello"], "Ciao!");
+	///
+	/// let imported = Localiser::from_export_data(&data, "en-US").unwrap();
+	/// assert_eq!(imported.get_attribute("login", "title", "en-US", None).unwrap(), "Log into Fluently");
+	/// assert_eq!(imported.get_message("hello", "it", None).unwrap(), "Ciao!");
+	/// assert_eq!(imported.export_data().unwrap(), data);
+	/// ```
+	pub fn export_data(&self) -> Result<ExportData> {
+		let mut languages = BTreeMap::new();
+		for language in self.available_languages.keys() {
+			let bundle = self.loaded_bundle(language)?;
+			let mut texts = self.catalog(language, CatalogMode::Formatted)?.messages
+				.into_iter()
+				.collect::<BTreeMap<_, _>>();
+			for term in self.term_entries(language) {
+				texts.insert(format!("-{}", term.id.name), bundle.format_pattern(&term.value, None, &mut Vec::new()).into_owned());
+			}
+			languages.insert(language.clone(), texts);
+		}
+
+		Ok(ExportData { languages })
+	}
+
+	/// Builds a [Localiser] out of the texts in an [ExportData], as if they had been loaded
+	/// through [`Self::try_load_from()`]. Texts are taken literally: anything that looks like a
+	/// placeable is escaped, so variables and selectors lost when exporting are not restored.
+	pub fn from_export_data(data: &ExportData, default_language: &str) -> Result<Self> {
+		let mut source = InMemory::new();
+		for (language, texts) in &data.languages {
+			source = source.with_resource(language, ExportData::to_ftl(texts)?);
+		}
+		Self::try_load_from(source, default_language)
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use std::{collections::BTreeMap, fmt};
+	use serde::ser::{self, Impossible, Serialize};
+	use crate::{export::json_string, InMemory, Localiser, LocaliserBuilder};
+	use super::CatalogMode;
+
+	#[derive(Debug)]
+	struct Unsupported;
+
+	impl fmt::Display for Unsupported {
+		fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+			f.write_str("unsupported")
+		}
+	}
+
+	impl std::error::Error for Unsupported {}
+
+	impl ser::Error for Unsupported {
+		fn custom<T: fmt::Display>(_: T) -> Self {
+			Self
+		}
+	}
+
+	/// A minimal JSON serialiser for strings, structs and maps. Keys are sorted, so that the
+	/// output does not depend on the order of a [HashMap](std::collections::HashMap).
+	struct Json;
+
+	/// The entries of a JSON object, serialised so far.
+	#[derive(Default)]
+	struct Object {
+		entries: BTreeMap<String, String>,
+		key: Option<String>
+	}
+
+	impl Object {
+		fn finish(self) -> String {
+			if self.entries.is_empty() {
+				return "{}".to_string();
+			}
+			let entries = self.entries.iter()
+				.map(|(key, value)| format!("\t{}: {}", key, value.replace('\n', "\n\t")))
+				.collect::<Vec<_>>();
+			format!("{{\n{}\n}}", entries.join(",\n"))
+		}
+	}
+
+	impl ser::SerializeMap for Object {
+		type Ok = String;
+		type Error = Unsupported;
+
+		fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Unsupported> {
+			self.key = Some(key.serialize(Json)?);
+			Ok(())
+		}
+
+		fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Unsupported> {
+			let key = self.key.take().ok_or(Unsupported)?;
+			self.entries.insert(key, value.serialize(Json)?);
+			Ok(())
+		}
+
+		fn end(self) -> Result<String, Unsupported> {
+			Ok(self.finish())
+		}
+	}
+
+	impl ser::SerializeStruct for Object {
+		type Ok = String;
+		type Error = Unsupported;
+
+		fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Unsupported> {
+			self.entries.insert(json_string(key), value.serialize(Json)?);
+			Ok(())
+		}
+
+		fn end(self) -> Result<String, Unsupported> {
+			Ok(self.finish())
+		}
+	}
+
+	macro_rules! unsupported {
+		($($name:ident($($ty:ty),*)),*) => {
+			$(fn $name(self, $(_: $ty),*) -> Result<String, Unsupported> {
+				Err(Unsupported)
+			})*
+		};
+	}
+
+	impl ser::Serializer for Json {
+		type Ok = String;
+		type Error = Unsupported;
+		type SerializeSeq = Impossible<String, Unsupported>;
+		type SerializeTuple = Impossible<String, Unsupported>;
+		type SerializeTupleStruct = Impossible<String, Unsupported>;
+		type SerializeTupleVariant = Impossible<String, Unsupported>;
+		type SerializeMap = Object;
+		type SerializeStruct = Object;
+		type SerializeStructVariant = Impossible<String, Unsupported>;
+
+		fn serialize_str(self, value: &str) -> Result<String, Unsupported> {
+			Ok(json_string(value))
+		}
+
+		fn serialize_map(self, _: Option<usize>) -> Result<Object, Unsupported> {
+			Ok(Object::default())
+		}
+
+		fn serialize_struct(self, _: &'static str, _: usize) -> Result<Object, Unsupported> {
+			Ok(Object::default())
+		}
+
+		unsupported!(
+			serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+			serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+			serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_bytes(&[u8]),
+			serialize_none(), serialize_unit(), serialize_unit_struct(&'static str),
+			serialize_unit_variant(&'static str, u32, &'static str)
+		);
+
+		fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<String, Unsupported> {
+			Err(Unsupported)
+		}
+
+		fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, _: &T) -> Result<String, Unsupported> {
+			Err(Unsupported)
+		}
+
+		fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _: &'static str, _: u32, _: &'static str, _: &T) -> Result<String, Unsupported> {
+			Err(Unsupported)
+		}
+
+		fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Unsupported> {
+			Err(Unsupported)
+		}
+
+		fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Unsupported> {
+			Err(Unsupported)
+		}
+
+		fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Unsupported> {
+			Err(Unsupported)
+		}
+
+		fn serialize_tuple_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeTupleVariant, Unsupported> {
+			Err(Unsupported)
+		}
+
+		fn serialize_struct_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeStructVariant, Unsupported> {
+			Err(Unsupported)
+		}
+	}
+
+	fn localiser() -> Localiser {
+		LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", "-brand = Fluently\nhello = Hello, { $name }!\nlogin = Log in\n    .title = Log into { -brand }\nquote = \"Quoted\"")
+			.with_resource("it", "hello = Ciao, { $name }!"), "en-US")
+			.configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+			.build().unwrap()
+	}
+
+	#[test]
+	fn source_matches_golden_file() {
+		let catalog = localiser().catalog("en-US", CatalogMode::Source).unwrap();
+		assert_eq!(catalog.serialize(Json).unwrap() + "\n", include_str!("../tests/golden/catalog-source.json"));
+	}
+
+	#[test]
+	fn formatted_matches_golden_file() {
+		let catalog = localiser().catalog("en", CatalogMode::Formatted).unwrap();
+		assert_eq!(catalog.serialize(Json).unwrap() + "\n", include_str!("../tests/golden/catalog-formatted.json"));
+	}
+}
@@ -0,0 +1,54 @@
+/// A piece of a pattern being converted into FTL from another format.
+#[cfg_attr(not(any(feature = "gettext", feature = "json-import")), allow(dead_code))]
+pub(crate) enum Piece<'a> {
+	/// Literal text, which may span multiple lines.
+	Text(&'a str),
+	/// A reference to the variable with the given name.
+	Variable(&'a str)
+}
+
+/// Appends a pattern made of the given pieces to some FTL source, escaping whatever would be read
+/// as syntax. Lines after the first are indented with `indent`.
+pub(crate) fn push_pattern(ftl: &mut String, pieces: &[Piece], indent: &str) {
+	ftl.push(' ');
+	let mut line_start = true;
+	for piece in pieces {
+		match piece {
+			Piece::Variable(name) => {
+				ftl.push_str("{ $");
+				ftl.push_str(name);
+				ftl.push_str(" }");
+			},
+			Piece::Text(text) => for c in text.chars() {
+				match c {
+					'\n' => {
+						ftl.push('\n');
+						ftl.push_str(indent);
+						line_start = true;
+						continue;
+					},
+					'\t' if line_start => ftl.push_str("{ \"\\u0009\" }"),
+					' ' | '[' | '*' | '.' if line_start => push_literal(ftl, c),
+					'{' | '}' => push_literal(ftl, c),
+					_ => ftl.push(c)
+				}
+				line_start = false;
+			}
+		}
+		line_start = false;
+	}
+}
+
+/// Appends a character as a string literal placeable.
+fn push_literal(ftl: &mut String, c: char) {
+	ftl.push_str("{ \"");
+	ftl.push(c);
+	ftl.push_str("\" }");
+}
+
+/// Checks whether some text is a valid Fluent identifier.
+pub(crate) fn is_identifier(text: &str) -> bool {
+	let mut chars = text.chars();
+	chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
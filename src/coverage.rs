@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::{error::{Error, Result}, Localiser, TypedFluentBundle};
+
+/// How well a single language covers the messages of the default one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageCoverage {
+	/// Messages defined in the default language but not in this one.
+	pub missing: Vec<String>,
+	/// Messages defined in this language but not in the default one.
+	pub extra: Vec<String>,
+	/// The number of messages defined in the default language.
+	pub total_default: usize
+}
+
+impl LanguageCoverage {
+	/// Whether this language defines every message of the default one.
+	pub fn is_complete(&self) -> bool {
+		self.missing.is_empty()
+	}
+}
+
+/// How well every language covers the messages of the default one.
+/// You can obtain a new instance by calling [`Localiser::check_coverage()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+	/// The language everything was compared against.
+	pub default_language: String,
+	/// A [HashMap] tying each non-default language identifier to its [LanguageCoverage].
+	pub languages: HashMap<String, LanguageCoverage>
+}
+
+impl CoverageReport {
+	/// Whether every language defines every message of the default one. Extra messages are not
+	/// taken into account.
+	pub fn is_complete(&self) -> bool {
+		self.languages.values().all(LanguageCoverage::is_complete)
+	}
+}
+
+/// The languages whose messages differ from the ones of the default language, as found by
+/// [`Localiser::assert_consistent()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyError {
+	/// The language everything was compared against.
+	pub default_language: String,
+	/// A [BTreeMap] tying the identifier of each language that differs to how. Keys are sorted
+	/// alphabetically.
+	pub languages: BTreeMap<String, LanguageCoverage>
+}
+
+/// What is wrong with a key checked by [`Localiser::verify_keys()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyProblemKind {
+	/// The message is not defined at all.
+	Missing,
+	/// The message is defined, but only has attributes.
+	NoValue
+}
+
+/// A key that failed verification in a certain language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyProblem {
+	/// The key that was checked.
+	pub key: String,
+	/// The language it was checked in.
+	pub language: String,
+	/// What is wrong with it.
+	pub kind: KeyProblemKind
+}
+
+/// Which languages [`Localiser::verify_keys_with()`] checks, and how strictly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerifyMode {
+	/// Every language must define every key.
+	#[default]
+	AllLanguages,
+	/// Only the default language is checked.
+	DefaultOnly,
+	/// Every language is checked, but only problems in the default language are errors: the
+	/// others are returned as warnings.
+	WarnNonDefault
+}
+
+impl Localiser {
+	/// Checks that every language defines a value for each of the given keys, failing with the
+	/// list of problems otherwise. Meant to be called at startup or in tests, with the list of
+	/// keys the application depends on.
+	pub fn verify_keys(&self, keys: &[&str]) -> std::result::Result<(), Vec<KeyProblem>> {
+		self.verify_keys_with(keys, VerifyMode::AllLanguages).map(|_| ())
+	}
+
+	/// Like [`Self::verify_keys()`], but checks according to the given [VerifyMode]. On success,
+	/// returns the problems that were only considered warnings.
+	pub fn verify_keys_with(&self, keys: &[&str], mode: VerifyMode) -> std::result::Result<Vec<KeyProblem>, Vec<KeyProblem>> {
+		let mut languages = self.available_languages.keys()
+			.filter(|lang| *lang != self.default_language())
+			.map(String::as_str)
+			.collect::<Vec<_>>();
+		languages.sort();
+		languages.insert(0, self.default_language());
+		if mode == VerifyMode::DefaultOnly {
+			languages.truncate(1);
+		}
+
+		let (mut errors, mut warnings) = (Vec::new(), Vec::new());
+		for language in languages {
+			for key in keys {
+				let kind = match self.message_entry(language, key) {
+					None => KeyProblemKind::Missing,
+					Some(msg) if msg.value.is_none() => KeyProblemKind::NoValue,
+					Some(_) => continue
+				};
+
+				let problem = KeyProblem { key: key.to_string(), language: language.to_string(), kind };
+				if mode == VerifyMode::WarnNonDefault && language != self.default_language() {
+					warnings.push(problem);
+				} else {
+					errors.push(problem);
+				}
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(warnings)
+		} else {
+			Err(errors)
+		}
+	}
+
+	/// Compares the messages defined by each language with the ones defined by the default one.
+	/// See [CoverageReport].
+	pub fn check_coverage(&self) -> CoverageReport {
+		let default_keys = self.message_keys(self.default_language()).unwrap_or_default();
+		let default_set = default_keys.iter().collect::<HashSet<_>>();
+
+		let languages = self.available_languages.keys()
+			.filter(|lang| *lang != self.default_language())
+			.map(|lang| {
+				let keys = self.message_keys(lang).unwrap_or_default();
+				let key_set = keys.iter().collect::<HashSet<_>>();
+				let coverage = LanguageCoverage {
+					missing: default_keys.iter().filter(|k| !key_set.contains(k)).cloned().collect(),
+					extra: keys.iter().filter(|k| !default_set.contains(k)).cloned().collect(),
+					total_default: default_keys.len()
+				};
+				(lang.clone(), coverage)
+			}).collect();
+
+		CoverageReport {
+			default_language: self.default_language().to_string(),
+			languages
+		}
+	}
+
+	/// Lists the messages of the given language whose value is exactly the same as in the
+	/// default language, once formatted without arguments, in the order they were loaded in.
+	/// Meant to catch strings that were copied over from the default language and never
+	/// actually translated, which [`Self::check_coverage()`] counts as present. Strings that are
+	/// legitimately the same in both, such as proper nouns, are listed too. Messages missing
+	/// from either language, or only having attributes, are not considered. Fails with
+	/// [`Error::MissingLanguageError`] if the language is not available.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!\nsave = Save")
+	///     .with_resource("it", "hello = Ciao!\nsave = Save");
+	/// let loc = Localiser::try_load_from(source, "en-US").unwrap();
+	/// assert_eq!(loc.untranslated("it").unwrap(), vec!["save"]);
+	/// ```
+	pub fn untranslated(&self, language: &str) -> Result<Vec<String>> {
+		let language = crate::fallback::parse_code(language)?.0;
+		if !self.available_languages.contains_key(&language) {
+			return Err(Error::MissingLanguageError(language));
+		}
+
+		let bundle = self.loaded_bundle(&language)?;
+		let default_bundle = self.loaded_bundle(self.default_language())?;
+		let format = |bundle: &TypedFluentBundle, key: &str| {
+			let pattern = bundle.get_message(&self.internal_key(key))?.value()?;
+			Some(bundle.format_pattern(pattern, None, &mut Vec::new()).into_owned())
+		};
+
+		Ok(self.message_keys(&language)?.into_iter()
+			.filter(|key| match (format(bundle, key), format(default_bundle, key)) {
+				(Some(value), Some(default)) => value == default,
+				_ => false
+			}).collect())
+	}
+
+	/// Checks that every language defines exactly the same messages as the default one, failing
+	/// with [`Error::Inconsistent`] otherwise. Unlike [`Self::check_coverage()`], extra messages
+	/// count as a problem too. Meant as a check to run in CI; the output is sorted, so that it's
+	/// the same on every run.
+	pub fn assert_consistent(&self) -> Result<()> {
+		let languages = self.check_coverage().languages.into_iter()
+			.filter(|(_, coverage)| !coverage.missing.is_empty() || !coverage.extra.is_empty())
+			.map(|(lang, mut coverage)| {
+				coverage.missing.sort();
+				coverage.extra.sort();
+				(lang, coverage)
+			})
+			.collect::<BTreeMap<_, _>>();
+
+		if languages.is_empty() {
+			Ok(())
+		} else {
+			Err(Error::Inconsistent(ConsistencyError {
+				default_language: self.default_language().to_string(),
+				languages
+			}))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{error::Error, InMemory, Localiser};
+	use super::{ConsistencyError, KeyProblem, KeyProblemKind, VerifyMode};
+
+	fn localiser() -> Localiser {
+		Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "menu-file = File\nmenu-edit = Edit\nlogin =\n    .title = Log in")
+			.with_resource("it", "menu-file = File\nmenu-edit =\n    .title = Modifica\nlogin =\n    .title = Accedi"), "en-US").unwrap()
+	}
+
+	fn problem(key: &str, language: &str, kind: KeyProblemKind) -> KeyProblem {
+		KeyProblem { key: key.to_string(), language: language.to_string(), kind }
+	}
+
+	#[test]
+	fn verifies_keys() {
+		let loc = localiser();
+		assert_eq!(loc.verify_keys(&["menu-file"]), Ok(()));
+		assert_eq!(loc.verify_keys(&["menu-file", "menu-edit", "menu-view"]), Err(vec![
+			problem("menu-view", "en-US", KeyProblemKind::Missing),
+			problem("menu-edit", "it", KeyProblemKind::NoValue),
+			problem("menu-view", "it", KeyProblemKind::Missing)
+		]));
+		assert_eq!(loc.verify_keys(&["login"]), Err(vec![
+			problem("login", "en-US", KeyProblemKind::NoValue),
+			problem("login", "it", KeyProblemKind::NoValue)
+		]));
+	}
+
+	#[test]
+	fn verifies_keys_by_mode() {
+		let loc = localiser();
+		assert_eq!(loc.verify_keys_with(&["menu-edit"], VerifyMode::DefaultOnly), Ok(vec![]));
+		assert_eq!(loc.verify_keys_with(&["menu-edit"], VerifyMode::WarnNonDefault), Ok(vec![
+			problem("menu-edit", "it", KeyProblemKind::NoValue)
+		]));
+		assert_eq!(loc.verify_keys_with(&["menu-view"], VerifyMode::WarnNonDefault), Err(vec![
+			problem("menu-view", "en-US", KeyProblemKind::Missing)
+		]));
+	}
+
+	#[test]
+	fn checks_coverage() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "hello = Hello!\nbye = Bye!\nsave = Save\nopen = Open")
+			.with_resource("it", "hello = Ciao!\nbye = Ciao!\nmenu = Menu")
+			.with_resource("fr", "hello = Bonjour !\nbye = Au revoir !\nsave = Enregistrer\nopen = Ouvrir"), "en-US").unwrap();
+		let report = loc.check_coverage();
+		assert_eq!(report.default_language, "en-US");
+		assert_eq!(report.languages.len(), 2);
+		assert!(!report.is_complete());
+
+		let it = &report.languages["it"];
+		assert_eq!(it.missing, ["save", "open"]);
+		assert_eq!(it.extra, ["menu"]);
+		assert_eq!(it.total_default, 4);
+		assert!(!it.is_complete());
+
+		let fr = &report.languages["fr"];
+		assert!(fr.missing.is_empty() && fr.extra.is_empty());
+		assert!(fr.is_complete());
+	}
+
+	#[test]
+	fn passes_when_consistent() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "hello = Hello!\nbye = Bye!")
+			.with_resource("fr", "bye = Au revoir !\nhello = Bonjour !"), "en-US").unwrap();
+		assert!(loc.assert_consistent().is_ok());
+	}
+
+	#[test]
+	fn fails_when_inconsistent() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "hello = Hello!\nbye = Bye!\nsave = Save\nopen = Open")
+			.with_resource("it", "hello = Ciao!\nmenu = Menu\nbye = Ciao!")
+			.with_resource("de", "hello = Hallo!\nbye = Tschüss!\nsave = Speichern\nopen = Öffnen\nzoom = Zoom\nclose = Schließen")
+			.with_resource("fr", "hello = Bonjour !\nbye = Au revoir !\nsave = Enregistrer\nopen = Ouvrir"), "en-US").unwrap();
+		let Err(Error::Inconsistent(ConsistencyError { default_language, languages })) = loc.assert_consistent() else {
+			panic!("expected an inconsistency");
+		};
+		assert_eq!(default_language, "en-US");
+		assert_eq!(languages.keys().collect::<Vec<_>>(), ["de", "it"]);
+		assert!(languages["de"].missing.is_empty());
+		assert_eq!(languages["de"].extra, ["close", "zoom"]);
+		assert_eq!(languages["it"].missing, ["open", "save"]);
+		assert_eq!(languages["it"].extra, ["menu"]);
+		assert_eq!(languages["it"].total_default, 4);
+	}
+}
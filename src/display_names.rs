@@ -0,0 +1,149 @@
+use unic_langid::LanguageIdentifier;
+use crate::Localiser;
+
+/// The names of common languages, as found in CLDR: the language subtag, the name of the
+/// language in itself, and its name in English. Names are capitalised as they would be at the
+/// start of a sentence, since that is how they appear in a list.
+const NAMES: &[(&str, &str, &str)] = &[
+	("ar", "العربية", "Arabic"),
+	("bg", "Български", "Bulgarian"),
+	("bn", "বাংলা", "Bangla"),
+	("ca", "Català", "Catalan"),
+	("cs", "Čeština", "Czech"),
+	("da", "Dansk", "Danish"),
+	("de", "Deutsch", "German"),
+	("el", "Ελληνικά", "Greek"),
+	("en", "English", "English"),
+	("eo", "Esperanto", "Esperanto"),
+	("es", "Español", "Spanish"),
+	("et", "Eesti", "Estonian"),
+	("eu", "Euskara", "Basque"),
+	("fa", "فارسی", "Persian"),
+	("fi", "Suomi", "Finnish"),
+	("fr", "Français", "French"),
+	("ga", "Gaeilge", "Irish"),
+	("he", "עברית", "Hebrew"),
+	("hi", "हिन्दी", "Hindi"),
+	("hr", "Hrvatski", "Croatian"),
+	("hu", "Magyar", "Hungarian"),
+	("id", "Indonesia", "Indonesian"),
+	("it", "Italiano", "Italian"),
+	("ja", "日本語", "Japanese"),
+	("ko", "한국어", "Korean"),
+	("lt", "Lietuvių", "Lithuanian"),
+	("lv", "Latviešu", "Latvian"),
+	("ms", "Melayu", "Malay"),
+	("nb", "Norsk bokmål", "Norwegian Bokmål"),
+	("nl", "Nederlands", "Dutch"),
+	("pl", "Polski", "Polish"),
+	("pt", "Português", "Portuguese"),
+	("ro", "Română", "Romanian"),
+	("ru", "Русский", "Russian"),
+	("sk", "Slovenčina", "Slovak"),
+	("sl", "Slovenščina", "Slovenian"),
+	("sr", "Српски", "Serbian"),
+	("sv", "Svenska", "Swedish"),
+	("th", "ไทย", "Thai"),
+	("tr", "Türkçe", "Turkish"),
+	("uk", "Українська", "Ukrainian"),
+	("vi", "Tiếng Việt", "Vietnamese"),
+	("zh", "中文", "Chinese")
+];
+
+impl Localiser {
+	/// Gives the name of a language, for instance to show in a language picker: by default its
+	/// name in itself, or its name in `in_language` if given. Only the names of common languages
+	/// in themselves and in English are bundled, so other combinations give [None]. If the code
+	/// has a region, it is added in parentheses (`pt-BR` → `Português (BR)`).
+	///
+	/// With [`LocaliserBuilder::display_name_fallback()`](crate::LocaliserBuilder::display_name_fallback),
+	/// the code itself is returned instead of [None], which also covers private-use and other
+	/// unknown codes.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello!"), "en-US").unwrap();
+	/// assert_eq!(loc.language_display_name("it", None).unwrap(), "Italiano");
+	/// assert_eq!(loc.language_display_name("it", Some("en-US")).unwrap(), "Italian");
+	/// assert_eq!(loc.language_display_name("pt-BR", None).unwrap(), "Português (BR)");
+	/// assert_eq!(loc.language_display_name("it", Some("de")), None);
+	/// ```
+	pub fn language_display_name(&self, language: &str, in_language: Option<&str>) -> Option<String> {
+		Self::display_name(language, in_language)
+			.or_else(|| self.options.display_name_fallback.then(|| language.to_string()))
+	}
+
+	/// Lists the code of each available language along with its name in itself, as given by
+	/// [`Self::language_display_name()`], falling back on the code if it has none. Names given in
+	/// the [Manifest](crate::manifest::Manifest) take precedence. Entries are
+	/// sorted by name, ignoring case, so that they can be shown as they are.
+	pub fn language_picker_entries(&self) -> Vec<(String, String)> {
+		let mut entries = self.available_languages.keys()
+			.map(|code| {
+				let name = self.manifest()
+					.and_then(|manifest| manifest.language(code)?.name.clone())
+					.or_else(|| Self::display_name(code, None));
+				(code.clone(), name.unwrap_or_else(|| code.clone()))
+			})
+			.collect::<Vec<_>>();
+		entries.sort_by_cached_key(|(code, name)| (name.to_lowercase(), code.clone()));
+		entries
+	}
+
+	/// Looks up the name of a language in the bundled table.
+	fn display_name(language: &str, in_language: Option<&str>) -> Option<String> {
+		let id = language.parse::<LanguageIdentifier>().ok()?;
+		let in_language = match in_language {
+			Some(in_language) => in_language.parse::<LanguageIdentifier>().ok()?.language,
+			None => id.language
+		};
+
+		let (_, autonym, english) = NAMES.iter().find(|(code, _, _)| id.language.as_str() == *code)?;
+		let name = if in_language == id.language {
+			autonym
+		} else if in_language.as_str() == "en" {
+			english
+		} else {
+			return None;
+		};
+
+		Some(match id.region {
+			Some(region) => format!("{} ({})", name, region),
+			None => name.to_string()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{InMemory, Localiser};
+
+	#[test]
+	fn lists_picker_entries() {
+		let source = ["it", "ja", "en-US", "qaa", "pt-BR", "de"].into_iter()
+			.fold(InMemory::new(), |source, language| source.with_resource(language, "hello = Hello!"));
+		let loc = Localiser::try_load_from(source, "en-US").unwrap();
+		let entries = loc.language_picker_entries();
+		assert_eq!(entries.iter().map(|(code, name)| (code.as_str(), name.as_str())).collect::<Vec<_>>(), [
+			("de", "Deutsch"),
+			("en-US", "English (US)"),
+			("it", "Italiano"),
+			("pt-BR", "Português (BR)"),
+			("qaa", "qaa"),
+			("ja", "日本語")
+		]);
+
+		let english = entries.iter()
+			.map(|(code, _)| loc.language_display_name(code, Some("en-US")))
+			.collect::<Vec<_>>();
+		assert_eq!(english, [
+			Some("German".to_string()),
+			Some("English (US)".to_string()),
+			Some("Italian".to_string()),
+			Some("Portuguese (BR)".to_string()),
+			None,
+			Some("Japanese".to_string())
+		]);
+	}
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+/// What to do when a message or term is defined more than once within the same language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+	/// Fail loading, as Fluent does by default.
+	#[default]
+	Error,
+	/// Let the definition loaded last win. Files are loaded in alphabetical order of their paths.
+	Override,
+	/// Like [`Self::Override`], but also keep track of what was shadowed. The list can be retrieved
+	/// through [`Localiser::duplicates()`](crate::Localiser::duplicates).
+	OverrideAndReport
+}
+
+/// A message or term that was defined more than once within the same language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+	/// The language the duplicate was found in.
+	pub language: String,
+	/// The identifier of the message or term; terms are prefixed with `-`, as they would be in FTL.
+	pub key: String,
+	/// The resource whose definition is used.
+	pub winning_file: PathBuf,
+	/// The resource whose definition was discarded.
+	pub shadowed_file: PathBuf
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+	use crate::{DuplicatePolicy, InMemory, LocaliserBuilder};
+
+	fn builder() -> LocaliserBuilder {
+		LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", "-brand = Fluently\nsave = Save\nopen = Open { -brand }")
+			.with_resource("en-US", "-brand = Fluentish\nsave = Save now\nclose = Close")
+			.with_resource("it", "save = Salva"), "en-US")
+	}
+
+	#[test]
+	fn error_fails_loading() {
+		assert!(builder().build().is_err());
+		assert!(builder().on_duplicate(DuplicatePolicy::Error).build().is_err());
+	}
+
+	#[test]
+	fn override_keeps_the_last_definition() {
+		let loc = builder().on_duplicate(DuplicatePolicy::Override).build().unwrap();
+		assert_eq!(loc.get_message("save", "en-US", None).unwrap(), "Save now");
+		assert_eq!(loc.get_message("open", "en-US", None).unwrap(), "Open Fluentish");
+		assert_eq!(loc.get_message("close", "en-US", None).unwrap(), "Close");
+		assert!(loc.duplicates().is_empty());
+	}
+
+	#[test]
+	fn override_and_report_records_shadowed_definitions() {
+		let loc = builder().on_duplicate(DuplicatePolicy::OverrideAndReport).build().unwrap();
+		assert_eq!(loc.get_message("save", "en-US", None).unwrap(), "Save now");
+		assert_eq!(loc.get_message("open", "en-US", None).unwrap(), "Open Fluentish");
+
+		let mut duplicates = loc.duplicates().to_vec();
+		duplicates.sort_by(|a, b| a.key.cmp(&b.key));
+		assert_eq!(duplicates.len(), 2);
+		for (duplicate, key) in duplicates.iter().zip(["-brand", "save"]) {
+			assert_eq!(duplicate.language, "en-US");
+			assert_eq!(duplicate.key, key);
+			assert_eq!(duplicate.winning_file, Path::new("en-US").join("1"));
+			assert_eq!(duplicate.shadowed_file, Path::new("en-US").join("0"));
+		}
+	}
+}
@@ -0,0 +1,62 @@
+use std::result::Result as StdResult;
+
+/// The characters bytes `0x80` to `0x9F` stand for in Windows-1252. The five bytes it leaves
+/// undefined are mapped to the control characters of the same value, as browsers do.
+const WINDOWS_1252_HIGH: [char; 32] = [
+	'\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+	'\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+	'\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+	'\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}'
+];
+
+/// The encoding localisation files are read in, set through
+/// [`LocaliserBuilder::encoding()`](crate::LocaliserBuilder::encoding). Everything is converted
+/// to UTF-8 before being parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+	/// Files must be valid UTF-8, or they fail to load.
+	#[default]
+	Utf8,
+	/// Files are read as Windows-1252, which most files exported as "ANSI" or "Latin-1" by
+	/// Western European versions of Windows actually are. Any file can be read this way.
+	Windows1252,
+	/// Files are read as ISO-8859-1, where each byte stands for the Unicode character of the same
+	/// value. Any file can be read this way.
+	Latin1,
+	/// Files starting with a byte order mark are read as the UTF-8 or UTF-16 it stands for, and
+	/// the mark is dropped. Other files are read as UTF-8 if they are valid UTF-8, and as
+	/// Windows-1252 otherwise.
+	Auto
+}
+
+impl Encoding {
+	/// Converts the raw contents of a file to a [String], failing with a description of what is
+	/// wrong if they are not valid in this encoding.
+	pub(crate) fn decode(self, bytes: Vec<u8>) -> StdResult<String, String> {
+		match self {
+			Self::Utf8 => String::from_utf8(bytes).map_err(|err| format!("UTF-8: {}", err)),
+			Self::Windows1252 => Ok(bytes.into_iter()
+				.map(|b| match b {
+					0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+					b => b as char
+				}).collect()),
+			Self::Latin1 => Ok(bytes.into_iter().map(char::from).collect()),
+			Self::Auto => match bytes.as_slice() {
+				[0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).map_err(|err| format!("UTF-8: {}", err)),
+				[0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+				[0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+				_ => String::from_utf8(bytes).or_else(|err| Self::Windows1252.decode(err.into_bytes()))
+			}
+		}
+	}
+}
+
+/// Decodes UTF-16 text whose code units are read with the given function.
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> StdResult<String, String> {
+	if !bytes.len().is_multiple_of(2) {
+		return Err("UTF-16: odd number of bytes".to_string());
+	}
+
+	String::from_utf16(&bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]])).collect::<Vec<_>>())
+		.map_err(|err| format!("UTF-16: {}", err))
+}
@@ -1,10 +1,29 @@
-use std::result::Result as StdResult;
+use std::{fmt, path::PathBuf, result::Result as StdResult};
 
 use fluent::FluentResource;
+use fluent_syntax::parser::ParserError;
+use crate::{coverage::ConsistencyError, merge::MergeConflict, validate::BrokenReference, Localiser};
 
 pub type Result<T> = StdResult<T, Error>;
 
 /// Simple wrapper around the errors that may occur during the program's execution.
+/// Implements [std::error::Error], and its [Display](fmt::Display) implementation gives a
+/// message meant for humans:
+///
+/// ```rust
+/// use fluent_fluently::{error::Error, InMemory, Localiser};
+///
+/// let source = InMemory::new().with_resource("en-US", "login =\n    .label = Log in");
+/// let loc = Localiser::try_load_from(source, "en-US").unwrap();
+///
+/// let err = loc.get_message("missing", "en-US", None).unwrap_err();
+/// assert_eq!(err.to_string(), "No such message missing for language en-US!");
+/// assert!(matches!(err, Error::MissingMessage { key, language } if key == "missing" && language == "en-US"));
+///
+/// let err = loc.get_message("login", "en-US", None).unwrap_err();
+/// assert!(matches!(err, Error::MessageHasNoValue { .. }));
+/// assert_eq!(loc.get_attribute("login", "label", "en-US", None).unwrap(), "Log in");
+/// ```
 #[derive(Debug)]
 pub enum Error {
 	/// A generic error - you are not supposed to ever actually encounter this, but it beats
@@ -17,8 +36,226 @@ pub enum Error {
 	LanguageIdentifierError(unic_langid::LanguageIdentifierError),
 	/// Wraps any number of [`fluent::FluentError`] that have occurred while parsing.
 	FluentError(Vec<fluent::FluentError>),
+	/// Happens when Fluent reports errors while formatting a message, such as a missing argument.
+	FormatErrors(Vec<FormatError>),
+	/// Happens when a resource is not valid text in the [Encoding](crate::Encoding) it is read in.
+	InvalidEncoding {
+		/// The identifier of the resource, usually its path.
+		path: PathBuf,
+		/// What is wrong with it.
+		reason: String
+	},
+	/// Happens when a language file or directory is left out because its name is not valid UTF-8,
+	/// and so can't be a language code.
+	InvalidFileName {
+		/// The path of the file or directory.
+		path: PathBuf
+	},
+	/// Happens when a resource could not be parsed while loading.
+	ParseError {
+		/// The identifier of the resource, usually its path.
+		path: PathBuf,
+		/// What went wrong, and where.
+		errors: Vec<AnnotatedParserError>
+	},
+	/// No longer returned, replaced by [`Self::MissingMessage`].
+	#[deprecated(note = "replaced by Error::MissingMessage")]
+	MissingMessageError(String),
 	/// Happens when you try to get a message that does not actually exist.
-	MissingMessageError(String)
+	MissingMessage {
+		/// The identifier of the message, written as it would be in FTL: terms are prefixed with
+		/// `-`, and attributes are given as `message.attribute`.
+		key: String,
+		/// The language that was requested, before any fallback.
+		language: String
+	},
+	/// Happens when you try to get the value of a message that only has attributes. They can
+	/// still be obtained through [`Localiser::get_attribute()`](crate::Localiser::get_attribute).
+	MessageHasNoValue {
+		/// The identifier of the message.
+		key: String,
+		/// The language that was requested, before any fallback.
+		language: String
+	},
+	/// Happens when you try to use a language that has not been loaded.
+	MissingLanguageError(String),
+	/// Happens when you try to remove the default language through
+	/// [`Localiser::remove_language()`](crate::Localiser::remove_language).
+	DefaultLanguageRemoval(String),
+	/// Happens when something needs the resources a language was built from, but they are not
+	/// known, because its bundle was handed over as it was through
+	/// [`Localiser::from_bundles()`](crate::Localiser::from_bundles).
+	UnknownResources(String),
+	/// Happens when one or more files could not be read, parsed or added to their bundle while
+	/// loading. Every file is tried before giving up, so that all of them are reported at once.
+	LoadErrors(LoadErrors),
+	/// Happens when no localisation was found for the default language while loading.
+	DefaultLanguageMissing(String),
+	/// Happens when nothing named after a valid language code was found at all while loading,
+	/// usually because the wrong directory was given.
+	///
+	/// ```rust
+	/// use fluent_fluently::{error::Error, InMemory, Localiser};
+	///
+	/// let err = Localiser::try_load_from(InMemory::new().with_resource("not a language", "hello = Hello!"), "en-US").unwrap_err();
+	/// assert!(matches!(err, Error::NoLanguagesLoaded));
+	/// ```
+	NoLanguagesLoaded,
+	/// Happens when some references could not be resolved while loading, if checking for them was
+	/// requested through [`LocaliserBuilder::validate_references()`](crate::LocaliserBuilder::validate_references).
+	BrokenReferences(Vec<BrokenReference>),
+	/// Happens when some languages don't define the same messages as the default one, if that
+	/// was checked through [`Localiser::assert_consistent()`](crate::Localiser::assert_consistent).
+	Inconsistent(ConsistencyError),
+	/// Happens when two localisers being merged define the same messages or terms, if that was
+	/// forbidden through [`MergePolicy::Error`](crate::MergePolicy::Error).
+	MergeConflicts(Vec<MergeConflict>),
+	/// Happens when the arguments passed don't match the variables used by a message, if
+	/// checking for it was requested through [`LocaliserBuilder::strict_args()`](crate::LocaliserBuilder::strict_args).
+	ArgumentMismatch {
+		/// Variables used by the message that were not passed.
+		missing: Vec<String>,
+		/// Arguments that were passed but are not used by the message.
+		unused: Vec<String>
+	}
+}
+
+/// A [ParserError], along with where it happened in the source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedParserError {
+	/// The original error.
+	pub error: ParserError,
+	/// The line the error was found on, starting from 1.
+	pub line: usize,
+	/// The column the error was found at, in characters, starting from 1.
+	pub column: usize,
+	/// The line the error was found on, as it appears in the source.
+	pub snippet: String
+}
+
+impl AnnotatedParserError {
+	/// Works out where an error happened in the given source text.
+	pub(crate) fn new(error: ParserError, source: &str) -> Self {
+		let offset = (0..=error.pos.start.min(source.len()))
+			.rev()
+			.find(|i| source.is_char_boundary(*i))
+			.unwrap_or_default();
+		let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+		let line_end = source[offset..].find('\n').map_or(source.len(), |i| offset + i);
+		Self {
+			line: source[..offset].matches('\n').count() + 1,
+			column: source[line_start..offset].chars().count() + 1,
+			snippet: source[line_start..line_end].trim_end_matches('\r').to_string(),
+			error
+		}
+	}
+}
+
+impl fmt::Display for AnnotatedParserError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "line {}, column {}: {}\n    {}", self.line, self.column, self.error, self.snippet)
+	}
+}
+
+/// An error Fluent reported while formatting a message, along with which message it was.
+/// Found in [`Error::FormatErrors`], or obtained through
+/// [`Localiser::format_errors()`](crate::Localiser::format_errors).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatError {
+	/// The identifier of the message, written as in [`Error::MissingMessage`].
+	pub key: String,
+	/// The language the message was taken from, after fallback.
+	pub language: String,
+	/// The original error.
+	pub source: fluent::FluentError
+}
+
+impl FormatError {
+	/// Wraps the errors Fluent reported while formatting a message in [`Error::FormatErrors`].
+	pub(crate) fn wrap(errors: Vec<fluent::FluentError>, key: &dyn fmt::Display, language: &str) -> Error {
+		Error::FormatErrors(errors.into_iter()
+			.map(|source| Self { key: key.to_string(), language: language.to_string(), source })
+			.collect())
+	}
+}
+
+impl fmt::Display for FormatError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} ({}): {}", self.key, self.language, self.source)
+	}
+}
+
+impl std::error::Error for FormatError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.source)
+	}
+}
+
+/// A file that could not be loaded, as reported by [`Error::LoadErrors`].
+#[derive(Debug)]
+pub struct LoadFailure {
+	/// The language the file belongs to.
+	pub language: String,
+	/// The identifier of the file, usually its path.
+	pub path: PathBuf,
+	/// What went wrong.
+	pub error: Error
+}
+
+/// Every file that could not be loaded, along with whatever could be loaded regardless.
+pub struct LoadErrors {
+	/// The files that failed, in the order they were read.
+	pub failures: Vec<LoadFailure>,
+	/// The [Localiser] made of the languages that loaded without problems, if there is one.
+	pub(crate) partial: Option<Box<Localiser>>
+}
+
+impl LoadErrors {
+	/// Returns the [Localiser] made of every language that loaded without problems, if the
+	/// default language is among them. Languages with any failed file are left out entirely,
+	/// and references are not validated even if that was requested.
+	///
+	/// ```rust
+	/// use fluent_fluently::{error::Error, InMemory, Localiser};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = { Ciao!")
+	///     .with_resource("it", "bye = { Arrivederci!");
+	/// let Err(Error::LoadErrors(errors)) = Localiser::try_load_from(source, "en-US") else { panic!() };
+	/// assert_eq!(errors.failures.len(), 2);
+	///
+	/// let loc = errors.into_partial().unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Hello!");
+	/// ```
+	pub fn into_partial(self) -> Option<Localiser> {
+		self.partial.map(|partial| *partial)
+	}
+
+	/// Like [`Self::into_partial()`], without consuming this.
+	pub fn partial(&self) -> Option<&Localiser> {
+		self.partial.as_deref()
+	}
+}
+
+impl fmt::Debug for LoadErrors {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("LoadErrors")
+			.field("failures", &self.failures)
+			.field("partial", &self.partial.is_some())
+			.finish()
+	}
+}
+
+impl From<Vec<LoadFailure>> for Error {
+	/// Unwraps a single failure, and wraps multiple ones in [`Error::LoadErrors`].
+	fn from(mut failures: Vec<LoadFailure>) -> Self {
+		if failures.len() == 1 {
+			failures.remove(0).error
+		} else {
+			Self::LoadErrors(LoadErrors { failures, partial: None })
+		}
+	}
 }
 
 impl From<std::io::Error> for Error {
@@ -45,3 +282,112 @@ impl From<unic_langid::LanguageIdentifierError> for Error {
 		Self::LanguageIdentifierError(err)
 	}
 }
+
+impl fmt::Display for Error {
+	#[allow(deprecated)]
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::GenericError(msg) | Self::MissingMessageError(msg) => write!(f, "{}", msg),
+			Self::MissingMessage { key, language } => write!(f, "No such message {} for language {}!", key, language),
+			Self::MessageHasNoValue { key, language } => write!(f, "Message {} has no value for language {}, only attributes!", key, language),
+			Self::IoError(err) => write!(f, "I/O error: {}", err),
+			Self::LanguageIdentifierError(err) => write!(f, "Invalid language identifier: {}", err),
+			Self::FluentError(errors) => match errors.as_slice() {
+				[err] => write!(f, "{}", err),
+				errors => {
+					write!(f, "{} Fluent errors:", errors.len())?;
+					for err in errors {
+						write!(f, "\n- {}", err)?;
+					}
+					Ok(())
+				}
+			},
+			Self::FormatErrors(errors) => match errors.as_slice() {
+				[err] => write!(f, "Failed to format {}", err),
+				errors => {
+					write!(f, "Failed to format messages, {} errors:", errors.len())?;
+					for err in errors {
+						write!(f, "\n- {}", err)?;
+					}
+					Ok(())
+				}
+			},
+			Self::InvalidEncoding { path, reason } => write!(f, "{} is not valid {}!", path.display(), reason),
+			Self::InvalidFileName { path } => write!(f, "The name of {} is not valid UTF-8!", path.display()),
+			Self::ParseError { path, errors } => {
+				write!(f, "Failed to parse {}:", path.display())?;
+				for err in errors {
+					write!(f, "\n- {}", err)?;
+				}
+				Ok(())
+			},
+			Self::LoadErrors(errors) => {
+				write!(f, "Failed to load {} file(s):", errors.failures.len())?;
+				for failure in &errors.failures {
+					let error = failure.error.to_string().replace('\n', "\n  ");
+					write!(f, "\n- {} ({}): {}", failure.path.display(), failure.language, error)?;
+				}
+				Ok(())
+			},
+			Self::MissingLanguageError(lang) => write!(f, "Language {} is not loaded!", lang),
+			Self::DefaultLanguageRemoval(lang) => write!(f, "Language {} is the default one and can't be removed!", lang),
+			Self::UnknownResources(lang) => write!(f, "The resources language {} was built from are not known!", lang),
+			Self::DefaultLanguageMissing(lang) => write!(f, "Nothing was found for the default language {}!", lang),
+			Self::NoLanguagesLoaded => write!(f, "No language was found, check that the localisation files are where they are expected to be!"),
+			Self::BrokenReferences(broken) => {
+				write!(f, "Some references could not be resolved:")?;
+				for reference in broken {
+					write!(f, "\n- {} in {} ({})", reference.reference, reference.message, reference.language)?;
+				}
+				Ok(())
+			},
+			Self::Inconsistent(inconsistent) => {
+				write!(f, "Some languages don't match {}:", inconsistent.default_language)?;
+				for (language, coverage) in &inconsistent.languages {
+					write!(f, "\n- {}", language)?;
+					if !coverage.missing.is_empty() {
+						write!(f, "; missing: {}", coverage.missing.join(", "))?;
+					}
+					if !coverage.extra.is_empty() {
+						write!(f, "; extra: {}", coverage.extra.join(", "))?;
+					}
+				}
+				Ok(())
+			},
+			Self::MergeConflicts(conflicts) => {
+				write!(f, "Some messages are defined by both localisers:")?;
+				for conflict in conflicts {
+					write!(f, "\n- {} ({})", conflict.key, conflict.language)?;
+				}
+				Ok(())
+			},
+			Self::ArgumentMismatch { missing, unused } => {
+				let list = |names: &[String]| names.iter()
+					.map(|name| format!("${}", name))
+					.collect::<Vec<_>>()
+					.join(", ");
+				write!(f, "The arguments don't match the message")?;
+				if !missing.is_empty() {
+					write!(f, "; missing: {}", list(missing))?;
+				}
+				if !unused.is_empty() {
+					write!(f, "; unused: {}", list(unused))?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	/// Returns the wrapped error, if there is exactly one.
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::IoError(err) => Some(err),
+			Self::LanguageIdentifierError(err) => Some(err),
+			Self::FluentError(errors) if errors.len() == 1 => Some(&errors[0]),
+			Self::FormatErrors(errors) if errors.len() == 1 => Some(&errors[0]),
+			_ => None
+		}
+	}
+}
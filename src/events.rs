@@ -0,0 +1,73 @@
+use std::sync::{mpsc, Arc, Mutex};
+use crate::Localiser;
+
+/// Sent to subscribers whenever the data loaded by a [Localiser] changes.
+/// See [`Localiser::subscribe()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReloadEvent {
+	/// The identifiers of the languages that were affected, in alphabetical order. Strings
+	/// obtained for them before the event may be out of date.
+	pub languages: Vec<String>
+}
+
+/// The channels a [Localiser] notifies of its changes. Cloning gives an empty list, so that
+/// each clone of a [Localiser] only notifies of its own changes, since they are copy-on-write.
+#[derive(Default)]
+pub(crate) struct Subscribers {
+	/// The sending halves of the channels.
+	senders: Arc<Mutex<Vec<mpsc::Sender<ReloadEvent>>>>
+}
+
+impl Clone for Subscribers {
+	fn clone(&self) -> Self {
+		Self::default()
+	}
+}
+
+impl Subscribers {
+	/// Sends an event to every subscriber, forgetting the ones that hung up.
+	pub(crate) fn notify(&self, event: ReloadEvent) {
+		self.senders.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.retain(|sender| sender.send(event.clone()).is_ok());
+	}
+
+	/// Adds a new subscriber, returning its receiving half.
+	pub(crate) fn subscribe(&self) -> mpsc::Receiver<ReloadEvent> {
+		let (sender, receiver) = mpsc::channel();
+		self.senders.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.push(sender);
+		receiver
+	}
+}
+
+impl Localiser {
+	/// Returns a channel that receives a [ReloadEvent] every time the loaded data changes, for
+	/// instance through [`Self::reload()`]. Meant for user interfaces, which can then refresh the
+	/// strings they display. Subscriptions end when the [Receiver](mpsc::Receiver) is dropped.
+	///
+	/// Only changes made to this instance are sent: clones, made before or after subscribing,
+	/// have subscribers of their own, since changing them does not change this. Subscriptions
+	/// are carried over by [`Self::into_shared()`], and from then on receive the changes made
+	/// through the [SharedLocaliser](crate::SharedLocaliser).
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser, ReloadEvent};
+	///
+	/// let mut loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!"), "en-US").unwrap();
+	/// let events = loc.subscribe();
+	///
+	/// let mut worker = loc.clone();
+	/// worker.remove_language("it").unwrap();
+	/// assert!(events.try_recv().is_err());
+	///
+	/// loc.remove_language("it").unwrap();
+	/// assert_eq!(events.try_recv().unwrap(), ReloadEvent { languages: vec!["it".to_string()] });
+	/// ```
+	pub fn subscribe(&self) -> mpsc::Receiver<ReloadEvent> {
+		self.subscribers.subscribe()
+	}
+}
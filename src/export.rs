@@ -0,0 +1,221 @@
+use std::{collections::HashSet, io};
+use fluent_syntax::{ast, serializer};
+use crate::{error::{Error, Result}, Localiser};
+
+/// The formats [`Localiser::export()`] can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+	/// Comma-separated values: a header row with `key` followed by the language codes, then one
+	/// row per message or attribute.
+	Csv,
+	/// A JSON object tying each message or attribute key to an object, which in turn ties each
+	/// language code to the corresponding text.
+	Json
+}
+
+/// Turns a pattern back into FTL source text. Multiline patterns lose the indentation they need
+/// within an FTL file.
+pub(crate) fn pattern_to_ftl(pattern: &ast::Pattern<&str>) -> String {
+	let resource = ast::Resource {
+		body: vec![ast::Entry::Message(ast::Message {
+			id: ast::Identifier { name: "x" },
+			value: Some(pattern.clone()),
+			attributes: Vec::new(),
+			comment: None
+		})]
+	};
+
+	let serialized = serializer::serialize(&resource);
+	let text = serialized.strip_prefix("x =")
+		.unwrap_or(&serialized)
+		.trim_end_matches('\n');
+	match text.strip_prefix(' ') {
+		Some(inline) => inline.to_string(),
+		None => text.trim_start_matches('\n')
+			.lines()
+			.map(|line| line.strip_prefix("    ").unwrap_or(line))
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+}
+
+/// Quotes a CSV field if needed.
+fn csv_field(field: &str) -> String {
+	if field.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	} else {
+		field.to_string()
+	}
+}
+
+/// Quotes and escapes a JSON string.
+pub(crate) fn json_string(value: &str) -> String {
+	let mut res = String::with_capacity(value.len() + 2);
+	res.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => res.push_str("\\\""),
+			'\\' => res.push_str("\\\\"),
+			'\n' => res.push_str("\\n"),
+			'\r' => res.push_str("\\r"),
+			'\t' => res.push_str("\\t"),
+			c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+			c => res.push(c)
+		}
+	}
+	res.push('"');
+	res
+}
+
+impl Localiser {
+	/// Writes the raw FTL text of every message and attribute in every language to the given
+	/// writer, in the requested [ExportFormat]. The default language comes first, followed by the
+	/// others in alphabetical order. See [`Self::export_languages()`].
+	pub fn export(&self, format: ExportFormat, writer: impl io::Write) -> Result<()> {
+		let mut languages = self.available_languages.keys()
+			.filter(|lang| *lang != self.default_language())
+			.map(String::as_str)
+			.collect::<Vec<_>>();
+		languages.sort();
+		languages.insert(0, self.default_language());
+		self.export_languages(format, &languages, writer)
+	}
+
+	/// Like [`Self::export()`], but only includes the given languages, in the given order.
+	/// There is one entry for each message and attribute found in any of them, keyed by message
+	/// identifier, or by `message.attribute` for attributes. Entries appear in the order they are
+	/// defined in, and texts missing from a language are left empty. Languages are given by their
+	/// codes, which are normalised; fails with [`Error::MissingLanguageError`] before writing
+	/// anything if any of them is not available.
+	///
+	/// ```rust
+	/// use fluent_fluently::{error::Error, ExportFormat, InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!\nbye = Bye, { $name }!")
+	///     .with_resource("it", "hello = Ciao!"), "en-US").unwrap();
+	///
+	/// let mut csv = Vec::new();
+	/// loc.export_languages(ExportFormat::Csv, &["IT", "en-us"], &mut csv).unwrap();
+	/// assert_eq!(String::from_utf8(csv).unwrap(), "key,it,en-US\nhello,Ciao!,Hello!\nbye,,\"Bye, { $name }!\"\n");
+	/// assert!(matches!(loc.export_languages(ExportFormat::Csv, &["it", "fr"], Vec::new()), Err(Error::MissingLanguageError(lang)) if lang == "fr"));
+	/// ```
+	pub fn export_languages(&self, format: ExportFormat, languages: &[&str], mut writer: impl io::Write) -> Result<()> {
+		let languages = languages.iter()
+			.map(|language| match crate::fallback::parse_code(language) {
+				Ok((code, _)) if self.available_languages.contains_key(&code) => Ok(code),
+				_ => Err(Error::MissingLanguageError(language.to_string()))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		let languages = languages.iter().map(String::as_str).collect::<Vec<_>>();
+
+		let mut keys = Vec::new();
+		let mut seen = HashSet::new();
+		for &language in &languages {
+			for msg in self.message_entries(language) {
+				let key = self.public_key(msg.id.name);
+				let attrs = msg.attributes.iter().map(|attr| format!("{}.{}", key, attr.id.name));
+				let msg_keys = msg.value.as_ref().map(|_| key.clone()).into_iter().chain(attrs);
+				for key in msg_keys {
+					if seen.insert(key.clone()) {
+						keys.push(key);
+					}
+				}
+			}
+		}
+
+		let text = |language: &str, key: &str| -> String {
+			let (id, attribute) = match key.split_once('.') {
+				Some((id, attribute)) => (id, Some(attribute)),
+				None => (key, None)
+			};
+
+			let msg = self.message_entry(language, id);
+			let pattern = match attribute {
+				Some(attribute) => msg.and_then(|msg| msg.attributes.iter().find(|attr| attr.id.name == attribute))
+					.map(|attr| &attr.value),
+				None => msg.and_then(|msg| msg.value.as_ref())
+			};
+			pattern.map(pattern_to_ftl).unwrap_or_default()
+		};
+
+		match format {
+			ExportFormat::Csv => {
+				let header = std::iter::once("key").chain(languages.iter().copied())
+					.map(csv_field)
+					.collect::<Vec<_>>();
+				writeln!(writer, "{}", header.join(","))?;
+				for key in &keys {
+					let row = std::iter::once(csv_field(key))
+						.chain(languages.iter().map(|lang| csv_field(&text(lang, key))))
+						.collect::<Vec<_>>();
+					writeln!(writer, "{}", row.join(","))?;
+				}
+			},
+			ExportFormat::Json => {
+				writeln!(writer, "{{")?;
+				for (i, key) in keys.iter().enumerate() {
+					let texts = languages.iter()
+						.map(|lang| format!("{}: {}", json_string(lang), json_string(&text(lang, key))))
+						.collect::<Vec<_>>();
+					let separator = if i + 1 < keys.len() { "," } else { "" };
+					writeln!(writer, "\t{}: {{ {} }}{}", json_string(key), texts.join(", "), separator)?;
+				}
+				writeln!(writer, "}}")?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{ExportFormat, InMemory, Localiser};
+
+	/// Splits CSV text into rows of unquoted fields.
+	fn parse_csv(text: &str) -> Vec<Vec<String>> {
+		let mut rows = Vec::new();
+		let mut row = Vec::new();
+		let mut field = String::new();
+		let mut quoted = false;
+		let mut chars = text.chars().peekable();
+		while let Some(c) = chars.next() {
+			match (c, quoted) {
+				('"', true) if chars.peek() == Some(&'"') => {
+					chars.next();
+					field.push('"');
+				},
+				('"', _) => quoted = !quoted,
+				(',', false) => row.push(std::mem::take(&mut field)),
+				('\n', false) => {
+					row.push(std::mem::take(&mut field));
+					rows.push(std::mem::take(&mut row));
+				},
+				(c, _) => field.push(c)
+			}
+		}
+		rows
+	}
+
+	#[test]
+	fn csv_parses_back() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "hello = Hello, \"world\"!\nbye = Bye, { $name }!\n    .title = Farewell\nmulti =\n    First line\n    Second line")
+			.with_resource("it", "hello = Ciao!\nbye = Ciao, { $name }!"), "en-US").unwrap();
+		let mut csv = Vec::new();
+		loc.export(ExportFormat::Csv, &mut csv).unwrap();
+		let rows = parse_csv(&String::from_utf8(csv).unwrap());
+
+		assert_eq!(rows[0], ["key", "en-US", "it"]);
+		assert_eq!(rows.len(), 5);
+		assert!(rows.iter().all(|row| row.len() == 3));
+		let cell = |key: &str, column: usize| rows.iter().find(|row| row[0] == key).map(|row| row[column].clone()).unwrap();
+		assert_eq!(cell("hello", 1), "Hello, \"world\"!");
+		assert_eq!(cell("hello", 2), "Ciao!");
+		assert_eq!(cell("bye", 2), "Ciao, { $name }!");
+		assert_eq!(cell("bye.title", 1), "Farewell");
+		assert_eq!(cell("bye.title", 2), "");
+		assert_eq!(cell("multi", 1), "First line\nSecond line");
+	}
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use unic_langid::{parser::ParserError, subtags::Script, LanguageIdentifier, LanguageIdentifierError};
+
+/// Lists the language codes to look for, in order, when the given one is requested: the code
+/// itself, followed by progressively less specific versions of it, obtained by dropping
+/// variants, region and script in this order (`sr-Latn-RS` → `sr-Latn` → `sr`).
+/// Trailing subtags that are not understood by [LanguageIdentifier], such as private-use ones,
+/// are dropped first (`it-IT-x-foo` → `it-IT` → `it`).
+pub(crate) fn fallback_chain(language: &str) -> Vec<String> {
+	let mut chain = vec![language.to_string()];
+	if let Some(mut id) = parse_lenient(language) {
+		let mut push = |id: &LanguageIdentifier| {
+			let code = id.to_string();
+			if !chain.contains(&code) {
+				chain.push(code);
+			}
+		};
+
+		push(&id);
+		id.clear_variants();
+		push(&id);
+		id.region = None;
+		push(&id);
+		id.script = None;
+		push(&id);
+	}
+
+	chain
+}
+
+/// Parses the given code as a [LanguageIdentifier], dropping trailing subtags it does not
+/// understand until it succeeds.
+fn parse_lenient(language: &str) -> Option<LanguageIdentifier> {
+	let mut code = language;
+	loop {
+		match code.parse::<LanguageIdentifier>() {
+			Ok(id) => return Some(id),
+			Err(_) => code = &code[..code.rfind('-')?]
+		}
+	}
+}
+
+/// Parses a language code, which may end with private-use subtags that [LanguageIdentifier]
+/// does not understand, such as `en-x-pirate` or `en-US-x-qa`. Returns the normalised code,
+/// private-use subtags included, along with the identifier of the rest.
+pub(crate) fn parse_code(code: &str) -> Result<(String, LanguageIdentifier), LanguageIdentifierError> {
+	let lower = code.to_ascii_lowercase();
+	let Some(start) = lower.find("-x-") else {
+		let id = code.parse::<LanguageIdentifier>()?;
+		return Ok((id.to_string(), id));
+	};
+
+	let id = code[..start].parse::<LanguageIdentifier>()?;
+	let private = &lower[start + 3..];
+	if !private.split('-').all(|subtag| (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())) {
+		return Err(ParserError::InvalidSubtag.into());
+	}
+	Ok((format!("{}-x-{}", id, private), id))
+}
+
+/// Checks whether a normalised language code has private-use subtags.
+pub(crate) fn is_private_use(code: &str) -> bool {
+	code.contains("-x-")
+}
+
+/// Finds the script a language is most likely written in, according to CLDR's likely subtags.
+fn likely_script(id: &LanguageIdentifier) -> Option<Script> {
+	let mut id = id.clone();
+	id.maximize();
+	id.script
+}
+
+/// Finds the available language that best matches the requested one, if any.
+/// Candidates from [`fallback_chain()`] are tried first, but only if they are likely written in
+/// the same script as the requested language: this way `zh-HK`, which is written in traditional
+/// characters, is not served by `zh`, which stands for simplified Chinese. Then, any language
+/// sharing both language subtag and script is considered, preferring those with the same region;
+/// this is what lets `zh-Hant-HK` be served by `zh-TW`. Only then are candidates from the chain
+/// with a different script accepted. Languages with private-use subtags, such as `en-x-pirate`,
+/// are only picked when requested exactly, ignoring case.
+pub(crate) fn negotiate<'a>(language: &str, available: &'a HashMap<String, LanguageIdentifier>) -> Option<&'a str> {
+	let find = |code: &str| available.get_key_value(code).map(|(code, id)| (code.as_str(), id));
+	if let Some((code, _)) = find(language) {
+		return Some(code);
+	}
+	if let Some((code, _)) = parse_code(language).ok().and_then(|(code, _)| find(&code)) {
+		return Some(code);
+	}
+
+	let requested = parse_lenient(language)?;
+	let script = likely_script(&requested);
+	let chain = fallback_chain(language).into_iter()
+		.filter_map(|code| find(&code))
+		.collect::<Vec<_>>();
+
+	if let Some((code, _)) = chain.iter().find(|(_, id)| likely_script(id) == script) {
+		return Some(code);
+	}
+
+	let mut same_script = available.iter()
+		.filter(|(code, id)| !is_private_use(code) && id.language == requested.language && likely_script(id) == script)
+		.collect::<Vec<_>>();
+	same_script.sort_by_key(|(code, id)| (id.region != requested.region, code.as_str()));
+	if let Some((code, _)) = same_script.first() {
+		return Some(code.as_str());
+	}
+
+	chain.first().map(|(code, _)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{InMemory, Localiser};
+
+	#[test]
+	fn chinese_scripts_are_told_apart() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "hello = Hello!")
+			.with_resource("zh-Hans", "hello = 你好！")
+			.with_resource("zh-Hant", "hello = 妳好！"), "en-US").unwrap();
+		assert_eq!(loc.resolve_language("zh-Hant-HK"), "zh-Hant");
+		assert_eq!(loc.resolve_language("zh-HK"), "zh-Hant");
+		assert_eq!(loc.resolve_language("zh-TW"), "zh-Hant");
+		assert_eq!(loc.resolve_language("zh-CN"), "zh-Hans");
+		assert_eq!(loc.resolve_language("zh"), "zh-Hans");
+		assert_eq!(loc.get_message("hello", "zh-Hant-HK", None).unwrap(), "妳好！");
+		assert_eq!(loc.get_message("hello", "zh-CN", None).unwrap(), "你好！");
+	}
+}
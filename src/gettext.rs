@@ -0,0 +1,388 @@
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+use unic_langid::LanguageIdentifier;
+use crate::{convert::{is_identifier, push_pattern, Piece}, error::{Error, Result}};
+
+/// How the key of a message converted from a gettext catalog is obtained from its `msgid`.
+/// Entries with a `msgctxt` get the context prepended to their key, separated by `-`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GettextKeys {
+	/// Lowercase the `msgid` and replace anything that is not an ASCII letter or digit with `-`,
+	/// so that `Hello, world!` becomes `hello-world`.
+	#[default]
+	Slug,
+	/// Use the `msgid` as it is. Entries whose `msgid` is not a valid Fluent identifier are
+	/// skipped.
+	Verbatim
+}
+
+/// A single translated entry of a gettext catalog.
+#[derive(Debug, Default)]
+struct PoEntry {
+	/// The disambiguating context, if any.
+	context: Option<String>,
+	/// The untranslated string.
+	id: String,
+	/// The untranslated plural string, if this entry has plural forms.
+	id_plural: Option<String>,
+	/// The translations, one per plural form if there are any.
+	translations: Vec<String>,
+	/// Whether the entry was marked as fuzzy, and should thus be treated as untranslated.
+	fuzzy: bool
+}
+
+/// The field of a [PoEntry] that continuation lines are appended to.
+#[derive(Clone, Copy)]
+enum Field {
+	Context,
+	Id,
+	IdPlural,
+	Translation(usize)
+}
+
+/// Converts the contents of a gettext `.po` file into FTL source text, to be used as a resource
+/// of the given language. Untranslated and fuzzy entries, as well as the header, are skipped.
+///
+/// Plural entries become select expressions on the `$count` variable, in which `%d` is replaced by
+/// `{ $count }`. Their forms are matched to the CLDR plural categories the language uses for
+/// integers, in the usual order (`zero`, `one`, `two`, `few`, `many`, `other`), which is how
+/// the `Plural-Forms` of nearly every catalog are laid out. If the number of forms does not
+/// match, the last one is used for every number. When the header declares `nplurals` in its
+/// `Plural-Forms`, it must match too, and entries with a different number of forms are an error.
+///
+/// ```rust
+/// use fluent_fluently::gettext::{po_to_ftl, GettextKeys};
+///
+/// let po = r#"
+/// msgid "Hello, world!"
+/// msgstr "Ciao, mondo!"
+///
+/// msgctxt "menu"
+/// msgid "Open"
+/// msgstr "Apri"
+///
+/// msgid "%d file"
+/// msgid_plural "%d files"
+/// msgstr[0] "%d file"
+/// msgstr[1] "%d file"
+///
+/// msgid "Untranslated"
+/// msgstr ""
+/// "#;
+///
+/// let ftl = po_to_ftl(po, &"it".parse().unwrap(), GettextKeys::Slug).unwrap();
+/// assert_eq!(ftl, "\
+/// hello-world = Ciao, mondo!
+/// menu-open = Apri
+/// file =
+///     { $count ->
+///         [one] { $count } file
+///        *[other] { $count } file
+///     }
+/// ");
+/// ```
+pub fn po_to_ftl(source: &str, language: &LanguageIdentifier, keys: GettextKeys) -> Result<String> {
+	let categories = integer_categories(language);
+	let entries = parse(source)?;
+	let plurals = entries.iter()
+		.find(|entry| entry.id.is_empty() && entry.context.is_none())
+		.and_then(|header| header.translations.first())
+		.and_then(|header| declared_plurals(header));
+	let mut ftl = String::new();
+	for entry in entries {
+		if let Some(plurals) = plurals.filter(|plurals| entry.id_plural.is_some() && entry.translations.len() != *plurals) {
+			return Err(Error::GenericError(format!("{} has {} plural forms, but the catalog declares {}!", entry.id, entry.translations.len(), plurals)));
+		}
+
+		if entry.fuzzy || entry.id.is_empty() || entry.translations.iter().all(|t| t.is_empty()) {
+			continue;
+		}
+
+		let Some(key) = make_key(&entry, keys) else {
+			continue;
+		};
+
+		ftl.push_str(&key);
+		ftl.push_str(" =");
+		if entry.id_plural.is_none() {
+			push_pattern(&mut ftl, &[Piece::Text(&entry.translations[0])], "    ");
+		} else {
+			// forms that can't be matched to categories are not matched to any, so the last one is used
+			let categories = match plurals {
+				Some(plurals) if plurals != categories.len() => &[][..],
+				_ => &categories[..]
+			};
+			push_plural(&mut ftl, &entry.translations, categories);
+		}
+		ftl.push('\n');
+	}
+
+	Ok(ftl)
+}
+
+/// Reads the number of plural forms from the `Plural-Forms` line of a catalog's header, such as
+/// `Plural-Forms: nplurals=2; plural=(n != 1);`.
+fn declared_plurals(header: &str) -> Option<usize> {
+	header.lines()
+		.find_map(|line| line.trim().strip_prefix("Plural-Forms:"))?
+		.split(';')
+		.find_map(|field| field.trim().strip_prefix("nplurals")?.trim_start().strip_prefix('='))?
+		.trim()
+		.parse()
+		.ok()
+}
+
+/// Lists the plural categories a language uses for integers, in CLDR order.
+fn integer_categories(language: &LanguageIdentifier) -> Vec<PluralCategory> {
+	const ORDER: [PluralCategory; 6] = [
+		PluralCategory::ZERO,
+		PluralCategory::ONE,
+		PluralCategory::TWO,
+		PluralCategory::FEW,
+		PluralCategory::MANY,
+		PluralCategory::OTHER
+	];
+
+	let Ok(rules) = PluralRules::create(language.clone(), PluralRuleType::CARDINAL) else {
+		return vec![PluralCategory::OTHER];
+	};
+	let found = (0..1000u32)
+		.filter_map(|n| rules.select(n).ok())
+		.collect::<Vec<_>>();
+	ORDER.into_iter()
+		.filter(|category| found.contains(category))
+		.collect()
+}
+
+/// The name of a plural category, as used for the keys of select expressions.
+fn category_name(category: &PluralCategory) -> &'static str {
+	match category {
+		PluralCategory::ZERO => "zero",
+		PluralCategory::ONE => "one",
+		PluralCategory::TWO => "two",
+		PluralCategory::FEW => "few",
+		PluralCategory::MANY => "many",
+		PluralCategory::OTHER => "other"
+	}
+}
+
+/// Obtains the key of the message an entry is converted into, if it can have one.
+fn make_key(entry: &PoEntry, keys: GettextKeys) -> Option<String> {
+	let convert = |text: &str| match keys {
+		GettextKeys::Slug => slug(text),
+		GettextKeys::Verbatim => Some(text.to_string())
+	};
+
+	let key = match &entry.context {
+		Some(context) => format!("{}-{}", convert(context)?, convert(&entry.id)?),
+		None => convert(&entry.id)?
+	};
+	is_identifier(&key).then_some(key)
+}
+
+/// Lowercases some text and replaces runs of characters that are not ASCII letters or digits with
+/// a single `-`. Leading characters that are not letters are dropped, and so are `printf`-style
+/// placeholders such as `%d` or `%1$s`.
+fn slug(text: &str) -> Option<String> {
+	let mut slug = String::new();
+	let mut chars = text.chars();
+	while let Some(c) = chars.next() {
+		if c == '%' {
+			chars.by_ref()
+				.find(|c| c.is_ascii_alphabetic() || *c == '%');
+			if !slug.is_empty() && !slug.ends_with('-') {
+				slug.push('-');
+			}
+		} else if c.is_ascii_alphanumeric() {
+			if slug.is_empty() && !c.is_ascii_alphabetic() {
+				continue;
+			}
+			slug.push(c.to_ascii_lowercase());
+		} else if !slug.is_empty() && !slug.ends_with('-') {
+			slug.push('-');
+		}
+	}
+
+	let slug = slug.trim_end_matches('-');
+	(!slug.is_empty()).then(|| slug.to_string())
+}
+
+/// Appends the plural forms of an entry as a select expression on `$count`.
+fn push_plural(ftl: &mut String, forms: &[String], categories: &[PluralCategory]) {
+	ftl.push_str("\n    { $count ->");
+	if forms.len() == categories.len() {
+		// languages like Russian only use `other` for fractions, so the last form is the default
+		for (i, (form, category)) in forms.iter().zip(categories).enumerate() {
+			push_variant(ftl, category_name(category), form, i == forms.len() - 1);
+		}
+	} else {
+		push_variant(ftl, "other", forms.last().unwrap(), true);
+	}
+	ftl.push_str("\n    }");
+}
+
+/// Appends a single variant of a select expression.
+fn push_variant(ftl: &mut String, key: &str, text: &str, default: bool) {
+	ftl.push_str(if default { "\n       *[" } else { "\n        [" });
+	ftl.push_str(key);
+	ftl.push(']');
+	let mut pieces = Vec::new();
+	for (i, text) in text.split("%d").enumerate() {
+		if i > 0 {
+			pieces.push(Piece::Variable("count"));
+		}
+		pieces.push(Piece::Text(text));
+	}
+	push_pattern(ftl, &pieces, "            ");
+}
+
+/// Parses the entries of a `.po` file.
+fn parse(source: &str) -> Result<Vec<PoEntry>> {
+	let mut entries = Vec::new();
+	let mut entry = PoEntry::default();
+	let mut field = None;
+	let mut fuzzy = false;
+
+	for (n, line) in source.lines().enumerate() {
+		let line = line.trim();
+		let invalid = || Error::GenericError(format!("Invalid gettext catalog at line {}!", n + 1));
+
+		if line.is_empty() || line.starts_with("#~") {
+			continue;
+		}
+
+		if let Some(flags) = line.strip_prefix("#,") {
+			fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+			continue;
+		}
+
+		if line.starts_with('#') {
+			continue;
+		}
+
+		if line.starts_with('"') {
+			let text = unquote(line).ok_or_else(invalid)?;
+			match field.ok_or_else(invalid)? {
+				Field::Context => entry.context.get_or_insert_with(String::new).push_str(&text),
+				Field::Id => entry.id.push_str(&text),
+				Field::IdPlural => entry.id_plural.get_or_insert_with(String::new).push_str(&text),
+				Field::Translation(i) => entry.translations[i].push_str(&text)
+			}
+			continue;
+		}
+
+		let (keyword, rest) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+		let text = unquote(rest.trim()).ok_or_else(invalid)?;
+
+		// a context or an id starts a new entry, unless it follows a context
+		let starts_entry = matches!(keyword, "msgctxt" | "msgid")
+			&& !(keyword == "msgid" && matches!(field, Some(Field::Context)));
+		if starts_entry && field.is_some() {
+			entries.push(std::mem::take(&mut entry));
+		}
+		if starts_entry {
+			entry.fuzzy = std::mem::take(&mut fuzzy);
+		}
+
+		field = Some(match keyword {
+			"msgctxt" => {
+				entry.context = Some(text);
+				Field::Context
+			},
+			"msgid" => {
+				entry.id = text;
+				Field::Id
+			},
+			"msgid_plural" => {
+				entry.id_plural = Some(text);
+				Field::IdPlural
+			},
+			"msgstr" => {
+				entry.translations = vec![text];
+				Field::Translation(0)
+			},
+			_ => {
+				let index = keyword.strip_prefix("msgstr[")
+					.and_then(|index| index.strip_suffix(']'))
+					.and_then(|index| index.parse::<usize>().ok())
+					.filter(|index| *index == entry.translations.len())
+					.ok_or_else(invalid)?;
+				entry.translations.push(text);
+				Field::Translation(index)
+			}
+		});
+	}
+
+	if field.is_some() {
+		entries.push(entry);
+	}
+
+	Ok(entries)
+}
+
+/// Removes the quotes around a string of a `.po` file and resolves its escape sequences.
+fn unquote(text: &str) -> Option<String> {
+	let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+	let mut unquoted = String::with_capacity(inner.len());
+	let mut chars = inner.chars();
+	while let Some(c) = chars.next() {
+		if c != '\\' {
+			unquoted.push(c);
+			continue;
+		}
+
+		unquoted.push(match chars.next()? {
+			'n' => '\n',
+			't' => '\t',
+			'r' => '\r',
+			other => other
+		});
+	}
+	Some(unquoted)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{po_to_ftl, GettextKeys};
+
+	const HEADER: &str = "msgid \"\"\nmsgstr \"\"\n\"Plural-Forms: nplurals=2; plural=(n != 1);\\n\"\n\n";
+	const PLURAL: &str = "msgid \"%d file\"\nmsgid_plural \"%d files\"\nmsgstr[0] \"One file\"\nmsgstr[1] \"%d files\"\n";
+
+	#[test]
+	fn checks_declared_plurals() {
+		let ftl = po_to_ftl(&format!("{}{}", HEADER, PLURAL), &"en".parse().unwrap(), GettextKeys::Slug).unwrap();
+		assert!(ftl.contains("[one] One file"));
+
+		// Polish has three categories for integers, so two forms can't be matched to them
+		let ftl = po_to_ftl(&format!("{}{}", HEADER, PLURAL), &"pl".parse().unwrap(), GettextKeys::Slug).unwrap();
+		assert!(!ftl.contains("[one]"));
+		assert!(ftl.contains("*[other] { $count } files"));
+
+		let three = PLURAL.replace("msgstr[1] \"%d files\"\n", "msgstr[1] \"%d files\"\nmsgstr[2] \"%d filez\"\n");
+		assert!(po_to_ftl(&format!("{}{}", HEADER, three), &"en".parse().unwrap(), GettextKeys::Slug).is_err());
+		assert!(po_to_ftl(&three, &"en".parse().unwrap(), GettextKeys::Slug).is_ok());
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn loads_catalogs_from_the_locale_tree() {
+		use crate::{fluent_args, DuplicatePolicy, Localiser};
+
+		// `save` is defined by both it/main.ftl and it/messages.po
+		assert!(Localiser::try_load("tests/gettext", "en-US").is_err());
+
+		let loc = Localiser::builder("tests/gettext", "en-US")
+			.on_duplicate(DuplicatePolicy::OverrideAndReport)
+			.configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+			.build().unwrap();
+		assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+		assert_eq!(loc.get_message("save", "it", None).unwrap(), "Salva il file");
+		assert_eq!(loc.get_message("file", "it", Some(&fluent_args!{ count => 1 })).unwrap(), "Un file");
+		assert_eq!(loc.get_message("file", "it", Some(&fluent_args!{ count => 3 })).unwrap(), "3 file");
+
+		let duplicates = loc.duplicates();
+		assert_eq!(duplicates.len(), 1);
+		assert_eq!(duplicates[0].key, "save");
+		assert!(duplicates[0].winning_file.ends_with("messages.po"));
+		assert!(duplicates[0].shadowed_file.ends_with("main.ftl"));
+	}
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use unic_langid::LanguageIdentifier;
+use crate::{error::{Error, FormatError, Result}, Localiser};
+
+/// A message defined in a certain language, as yielded by [`Localiser::iter()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageInfo {
+	/// The language the message is defined in, without any private-use subtags its code has.
+	pub language: LanguageIdentifier,
+	/// The identifier of the message.
+	pub key: String,
+	/// Whether the message has a value, rather than only attributes.
+	pub has_value: bool,
+	/// The names of the attributes of the message, in the order they are defined in.
+	pub attributes: Vec<String>
+}
+
+impl Localiser {
+	/// Iterates over every message of every language. Languages come in alphabetical order of
+	/// their codes, and the messages of each language in the order they were loaded in, so the
+	/// order is the same on every run. Languages that are loaded lazily are loaded as they are
+	/// reached.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("it", "hello = Ciao!")
+	///     .with_resource("en-US", "hello = Hello!\nlogin =\n    .title = Log in"), "en-US").unwrap();
+	/// let messages = loc.iter()
+	///     .map(|msg| format!("{}:{}", msg.language, msg.key))
+	///     .collect::<Vec<_>>();
+	/// assert_eq!(messages, ["en-US:hello", "en-US:login", "it:hello"]);
+	/// ```
+	pub fn iter(&self) -> impl Iterator<Item = MessageInfo> + '_ {
+		let mut languages = self.available_languages.iter().collect::<Vec<_>>();
+		languages.sort_by_key(|(code, _)| *code);
+		languages.into_iter().flat_map(|(code, id)| {
+			self.message_entries(code).map(|msg| MessageInfo {
+				language: id.clone(),
+				key: self.public_key(msg.id.name),
+				has_value: msg.value.is_some(),
+				attributes: msg.attributes.iter().map(|attr| attr.id.name.to_string()).collect()
+			})
+		})
+	}
+
+	/// Like [`Self::iter()`], but only yields the code of each language along with the key of
+	/// each message, in the same order.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("it", "hello = Ciao!")
+	///     .with_resource("en-US", "hello = Hello!\nbye = Bye!"), "en-US").unwrap();
+	/// let messages = loc.iter_messages().collect::<Vec<_>>();
+	/// assert_eq!(messages, [("en-US", "hello".to_string()), ("en-US", "bye".to_string()), ("it", "hello".to_string())]);
+	/// ```
+	pub fn iter_messages(&self) -> impl Iterator<Item = (&str, String)> + '_ {
+		let mut languages = self.available_languages.keys().collect::<Vec<_>>();
+		languages.sort();
+		languages.into_iter().flat_map(move |code| {
+			self.message_entries(code).map(move |msg| (code.as_str(), self.public_key(msg.id.name)))
+		})
+	}
+
+	/// Formats every message of exactly the given language without arguments, as
+	/// [`Self::get_message()`] would, tying each key to the result. Handy to check that nothing
+	/// fails or panics. Messages that only have attributes give
+	/// [`Error::MessageHasNoValue`](crate::error::Error::MessageHasNoValue). The map is empty if
+	/// the language is not available.
+	pub fn format_all(&self, language: &str) -> HashMap<String, Result<String>> {
+		let Ok((code, _)) = crate::fallback::parse_code(language) else {
+			return HashMap::new();
+		};
+
+		self.message_entries(&code)
+			.map(|msg| {
+				let key = self.public_key(msg.id.name);
+				let res = self.get_message(&key, &code, None);
+				(key, res)
+			}).collect()
+	}
+
+	/// Formats every message of exactly the given language without arguments, like
+	/// [`Self::format_all()`], and returns the first `limit` errors Fluent reported, in the order
+	/// messages were loaded in, each tied to the message it came from. Messages that could not be
+	/// formatted for other reasons, such as only having attributes, are not included.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello, { $name }!\nbye = Bye, { $name } and { $other }!"), "en-US").unwrap();
+	/// let errors = loc.format_errors("en-US", 2);
+	/// assert_eq!(errors.iter().map(|err| err.key.as_str()).collect::<Vec<_>>(), ["hello", "bye"]);
+	/// assert_eq!(errors[0].to_string(), "hello (en-US): Resolver error: Unknown variable: $name");
+	/// ```
+	pub fn format_errors(&self, language: &str, limit: usize) -> Vec<FormatError> {
+		let Ok((code, _)) = crate::fallback::parse_code(language) else {
+			return Vec::new();
+		};
+
+		self.message_entries(&code)
+			.flat_map(|msg| match self.get_message(&self.public_key(msg.id.name), &code, None) {
+				Err(Error::FormatErrors(errors)) => errors,
+				_ => Vec::new()
+			})
+			.take(limit)
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{InMemory, LocaliserBuilder};
+
+	#[test]
+	fn format_all_only_loads_the_language() {
+		let source = InMemory::new()
+			.with_resource("en-US", "hello = Hello!\nlogin =\n    .title = Log in")
+			.with_resource("it", "hello = Ciao!")
+			.with_resource("fr", "hello = Bonjour !");
+		let loc = LocaliserBuilder::with_source(source, "en-US").lazy(true).build().unwrap();
+		let formatted = loc.format_all("it");
+		assert_eq!(formatted.len(), 1);
+		assert_eq!(formatted["hello"].as_ref().unwrap(), "Ciao!");
+		assert!(loc.loaded_resources("fr").is_none());
+		assert!(loc.format_all("de").is_empty());
+	}
+
+	#[test]
+	fn format_errors_only_loads_the_language() {
+		let source = InMemory::new()
+			.with_resource("en-US", "hello = Hello, { $name }!")
+			.with_resource("it", "hello = Ciao, { $name }!\nbye = Ciao, { $a } e { $b }!")
+			.with_resource("fr", "hello = Bonjour, { $name } !");
+		let loc = LocaliserBuilder::with_source(source, "en-US").lazy(true).build().unwrap();
+		let errors = loc.format_errors("it", 2);
+		assert_eq!(errors.iter().map(|err| err.key.as_str()).collect::<Vec<_>>(), ["hello", "bye"]);
+		assert!(errors.iter().all(|err| err.language == "it"));
+		assert!(loc.loaded_resources("fr").is_none());
+	}
+}
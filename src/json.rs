@@ -0,0 +1,219 @@
+use std::{iter::Peekable, str::Chars};
+use crate::{convert::{is_identifier, push_pattern, Piece}, error::{Error, Result}};
+
+/// Converts the contents of an i18next-style JSON file into FTL source text. The file must hold
+/// an object, whose nested keys are joined with `separator` to form the keys of the messages:
+/// with `-`, `{"menu": {"file": "File"}}` becomes `menu-file = File`. Interpolations such as
+/// `{{name}}` become references to the variable with the same name.
+///
+/// Fails if anything other than an object or a string is found, or if a flattened key is not a
+/// valid Fluent identifier.
+///
+/// ```rust
+/// use fluent_fluently::json::json_to_ftl;
+///
+/// let json = r#"{
+///     "menu": { "file": "File", "edit": "Edit" },
+///     "greeting": "Hello, {{name}}!"
+/// }"#;
+///
+/// let ftl = json_to_ftl(json, "-").unwrap();
+/// assert_eq!(ftl, "\
+/// menu-file = File
+/// menu-edit = Edit
+/// greeting = Hello, { $name }!
+/// ");
+///
+/// assert!(json_to_ftl(r#"{"list": ["a", "b"]}"#, "-").is_err());
+/// ```
+pub fn json_to_ftl(source: &str, separator: &str) -> Result<String> {
+	let mut parser = Parser { chars: source.chars().peekable() };
+	let mut messages = Vec::new();
+	parser.skip_whitespace();
+	parser.object(None, separator, &mut messages)?;
+	parser.skip_whitespace();
+	if parser.chars.peek().is_some() {
+		return Err(invalid("unexpected content after the top-level object"));
+	}
+
+	let mut ftl = String::new();
+	for (key, text) in messages {
+		if !is_identifier(&key) {
+			return Err(invalid(&format!("\"{}\" is not a valid message key", key)));
+		}
+
+		ftl.push_str(&key);
+		ftl.push_str(" =");
+		push_pattern(&mut ftl, &interpolations(&text), "    ");
+		ftl.push('\n');
+	}
+
+	Ok(ftl)
+}
+
+/// Splits some text on the `{{variable}}` interpolations it contains. Formatting options, as in
+/// `{{date, short}}`, are ignored.
+fn interpolations(text: &str) -> Vec<Piece<'_>> {
+	let mut pieces = Vec::new();
+	let mut rest = text;
+	while let Some(start) = rest.find("{{") {
+		let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+			break;
+		};
+
+		let name = rest[start + 2..end].split(',').next().unwrap_or_default().trim();
+		if is_identifier(name) {
+			pieces.push(Piece::Text(&rest[..start]));
+			pieces.push(Piece::Variable(name));
+		} else {
+			pieces.push(Piece::Text(&rest[..end + 2]));
+		}
+		rest = &rest[end + 2..];
+	}
+
+	pieces.push(Piece::Text(rest));
+	pieces
+}
+
+/// Creates the error returned for malformed or unsupported JSON.
+fn invalid(reason: &str) -> Error {
+	Error::GenericError(format!("Invalid JSON translation file: {}!", reason))
+}
+
+/// A minimal JSON parser, only accepting nested objects with string leaves.
+struct Parser<'s> {
+	/// The characters yet to be read.
+	chars: Peekable<Chars<'s>>
+}
+
+impl Parser<'_> {
+	/// Skips any whitespace.
+	fn skip_whitespace(&mut self) {
+		while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+	}
+
+	/// Consumes the given character, after any whitespace, failing if something else is found.
+	fn expect(&mut self, expected: char) -> Result<()> {
+		self.skip_whitespace();
+		match self.chars.next() {
+			Some(c) if c == expected => Ok(()),
+			Some(c) => Err(invalid(&format!("expected '{}', found '{}'", expected, c))),
+			None => Err(invalid(&format!("expected '{}', found the end of the file", expected)))
+		}
+	}
+
+	/// Parses an object, adding its string leaves to `messages` with their keys prefixed by
+	/// `prefix`.
+	fn object(&mut self, prefix: Option<&str>, separator: &str, messages: &mut Vec<(String, String)>) -> Result<()> {
+		self.expect('{')?;
+		self.skip_whitespace();
+		if self.chars.next_if_eq(&'}').is_some() {
+			return Ok(());
+		}
+
+		loop {
+			self.expect('"')?;
+			let key = match prefix {
+				Some(prefix) => format!("{}{}{}", prefix, separator, self.string()?),
+				None => self.string()?
+			};
+			self.expect(':')?;
+			self.skip_whitespace();
+			match self.chars.peek() {
+				Some('{') => self.object(Some(&key), separator, messages)?,
+				Some('"') => {
+					self.chars.next();
+					messages.push((key, self.string()?));
+				},
+				Some('[') => return Err(invalid(&format!("\"{}\" is an array, only objects and strings are supported", key))),
+				Some(_) => return Err(invalid(&format!("\"{}\" is not a string, only objects and strings are supported", key))),
+				None => return Err(invalid("unexpected end of the file"))
+			}
+
+			self.skip_whitespace();
+			match self.chars.next() {
+				Some(',') => continue,
+				Some('}') => return Ok(()),
+				_ => return Err(invalid("expected ',' or '}' after a value"))
+			}
+		}
+	}
+
+	/// Parses the rest of a string, whose opening quote was already consumed.
+	fn string(&mut self) -> Result<String> {
+		let mut string = String::new();
+		loop {
+			match self.chars.next().ok_or_else(|| invalid("unterminated string"))? {
+				'"' => return Ok(string),
+				'\\' => string.push(self.escape()?),
+				c => string.push(c)
+			}
+		}
+	}
+
+	/// Parses the rest of an escape sequence, whose backslash was already consumed.
+	fn escape(&mut self) -> Result<char> {
+		Ok(match self.chars.next().ok_or_else(|| invalid("unterminated string"))? {
+			'n' => '\n',
+			't' => '\t',
+			'r' => '\r',
+			'b' => '\u{8}',
+			'f' => '\u{c}',
+			'u' => {
+				let high = self.hex()?;
+				if !(0xD800..0xDC00).contains(&high) {
+					return char::from_u32(high).ok_or_else(|| invalid("invalid unicode escape"));
+				}
+
+				// surrogate pair
+				self.expect('\\')?;
+				self.expect('u')?;
+				let low = self.hex()?;
+				if !(0xDC00..0xE000).contains(&low) {
+					return Err(invalid("invalid surrogate pair"));
+				}
+				char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+					.ok_or_else(|| invalid("invalid unicode escape"))?
+			},
+			c => c
+		})
+	}
+
+	/// Parses the four hexadecimal digits of a unicode escape.
+	fn hex(&mut self) -> Result<u32> {
+		let digits = self.chars.by_ref().take(4).collect::<String>();
+		u32::from_str_radix(&digits, 16).map_err(|_| invalid("invalid unicode escape"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::json_to_ftl;
+
+	#[test]
+	fn checks_surrogate_pairs() {
+		assert_eq!(json_to_ftl(r#"{"smile": "\ud83d\ude00"}"#, "-").unwrap(), "smile = \u{1F600}\n");
+		assert!(json_to_ftl(r#"{"smile": "\ud83dA"}"#, "-").is_err());
+		assert!(json_to_ftl(r#"{"smile": "\ud83d\ud83d"}"#, "-").is_err());
+		assert!(json_to_ftl(r#"{"smile": "\ude00"}"#, "-").is_err());
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn loads_nested_files() {
+		use crate::{fluent_args, Localiser};
+
+		let loc = Localiser::builder("tests/json", "en-US")
+			.json_separator("_")
+			.configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+			.build().unwrap();
+		assert_eq!(loc.get_message("menu_edit_undo", "en-US", None).unwrap(), "Undo");
+		assert_eq!(loc.get_message("menu_edit_undo", "it", None).unwrap(), "Annulla");
+		assert_eq!(loc.get_message("emoji", "en-US", None).unwrap(), "Smile \u{1F600}");
+		assert!(!loc.has_message("menu_edit_redo", "it"));
+
+		let args = fluent_args!{ name => "Anna", count => 3 };
+		assert_eq!(loc.get_message("inbox_greeting", "en-US", Some(&args)).unwrap(), "Hello, Anna! You have 3 messages.");
+		assert_eq!(loc.get_message("inbox_greeting", "it", Some(&args)).unwrap(), "Ciao, Anna! Hai 3 messaggi.");
+	}
+}
@@ -0,0 +1,102 @@
+use std::{fmt, sync::Arc};
+use fluent::FluentArgs;
+use crate::{error::{Error, Result}, Localiser};
+
+/// The key of a message that was checked to exist in the default language, so that typos are
+/// found once at startup rather than whenever the message is needed. You can obtain a new
+/// instance by calling [`Localiser::message_key()`], and use it through [`Localiser::get()`].
+/// Cloning is cheap.
+///
+/// ```rust
+/// use fluent_fluently::{InMemory, Localiser};
+///
+/// let loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello!"), "en-US").unwrap();
+/// let hello = loc.message_key("hello").unwrap();
+/// assert_eq!(loc.get(&hello, "it", None).unwrap(), "Hello!");
+/// assert!(loc.message_key("helo").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MessageKey(Arc<str>);
+
+impl MessageKey {
+	/// The key as a string.
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+}
+
+impl AsRef<str> for MessageKey {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+impl fmt::Display for MessageKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+/// The key of a message that was checked to exist in the default language at compile time.
+/// Instances are generated by the `messages!` macro, available with the `derive` feature, which
+/// creates a constant for each message of the default language:
+///
+/// ```rust,ignore
+/// fluent_fluently::messages!("locale", "en-US");
+///
+/// let loc = fluent_fluently::Localiser::try_load("./locale", "en-US")?;
+/// let msg = messages::HELLO_WORLD.get(&loc, "it", None)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StaticMessageKey(&'static str);
+
+impl StaticMessageKey {
+	/// Creates a new instance without checking anything. Only meant to be used by the
+	/// `messages!` macro.
+	#[doc(hidden)]
+	pub const fn __new(key: &'static str) -> Self {
+		Self(key)
+	}
+
+	/// The key as a string.
+	pub const fn as_str(&self) -> &'static str {
+		self.0
+	}
+
+	/// Extracts the message from the given [Localiser]. See [`Localiser::get_message()`].
+	pub fn get(&self, localiser: &Localiser, language: &str, args: Option<&FluentArgs>) -> Result<String> {
+		localiser.get_message(self.0, language, args)
+	}
+}
+
+impl AsRef<str> for StaticMessageKey {
+	fn as_ref(&self) -> &str {
+		self.0
+	}
+}
+
+impl fmt::Display for StaticMessageKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.0)
+	}
+}
+
+impl Localiser {
+	/// Checks that the default language defines a message with the given key, failing with
+	/// [`Error::MissingMessage`] otherwise, and returns a [MessageKey] for it. The key is only
+	/// checked once: if the message is removed later on, for instance by a reload, looking it up
+	/// fails as usual.
+	pub fn message_key(&self, key: &str) -> Result<MessageKey> {
+		let bundle = self.resolve_bundle(self.default_language())?;
+		if !bundle.has_message(&self.internal_key(key)) {
+			return Err(Error::MissingMessage { key: key.to_string(), language: self.default_language().to_string() });
+		}
+
+		Ok(MessageKey(key.into()))
+	}
+
+	/// Like [`Self::get_message()`], but takes a [MessageKey].
+	pub fn get(&self, key: &MessageKey, language: &str, args: Option<&FluentArgs>) -> Result<String> {
+		self.get_message(key.as_str(), language, args)
+	}
+}
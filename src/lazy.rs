@@ -0,0 +1,141 @@
+use std::{path::PathBuf, sync::OnceLock};
+use unic_langid::LanguageIdentifier;
+use crate::{error::{Error, Result}, stats::LanguageStats, LoadedResource, Localiser, TypedFluentBundle};
+
+/// A language whose resources are only read and parsed the first time it is needed.
+pub(crate) struct LazyLanguage {
+	/// The identifier of the language.
+	id: LanguageIdentifier,
+	/// The identifiers of the resources making up the language, to be read from the source.
+	paths: Vec<PathBuf>,
+	/// The language, once it was loaded.
+	loaded: OnceLock<LoadedLanguage>
+}
+
+/// The bundle of a language that was loaded lazily, along with what it was built from.
+pub(crate) struct LoadedLanguage {
+	/// The bundle.
+	pub(crate) bundle: TypedFluentBundle,
+	/// The resources the bundle was built from.
+	pub(crate) resources: Vec<LoadedResource>,
+	/// How much content the language holds.
+	pub(crate) stats: LanguageStats
+}
+
+impl LazyLanguage {
+	/// Creates a new instance that will load the given resources.
+	pub(crate) fn new(id: LanguageIdentifier, paths: Vec<PathBuf>) -> Self {
+		Self { id, paths, loaded: OnceLock::new() }
+	}
+}
+
+impl Localiser {
+	/// Loads the given language right away, if it was set to be loaded lazily through
+	/// [`LocaliserBuilder::lazy()`](crate::LocaliserBuilder::lazy) and was not used yet. Does
+	/// nothing if the language was already loaded, and fails with
+	/// [`Error::MissingLanguageError`] if it is not available at all.
+	///
+	/// Lazily loaded languages are loaded the first time anything needs their content: formatting
+	/// a message, listing keys, but also operations spanning every language, such as
+	/// [`Self::stats()`] or [`Self::validate_references()`]. Loading only needs a shared
+	/// reference, so it is safe to use a [Localiser] from multiple threads. If several threads
+	/// need a language that is not loaded yet at the same time, each of them may read and parse
+	/// it, but only one result is kept and used from then on. A language failing to load is not
+	/// remembered: the error is returned, and loading is tried again the next time.
+	pub fn preload(&self, language: &str) -> Result<()> {
+		let language = crate::fallback::parse_code(language)?.0;
+		if self.bundles.contains_key(&language) {
+			return Ok(());
+		}
+
+		let lazy = self.lazy.get(&language)
+			.ok_or(Error::MissingLanguageError(language))?;
+		self.load_lazy(lazy).map(|_| ())
+	}
+
+	/// Like [`Self::preload()`], for each of the given languages in turn. Meant to be called at
+	/// startup with the languages that are used the most, so that their first use is as fast as
+	/// any other, while the rest are still only loaded when needed. Stops at the first language
+	/// that fails to load.
+	pub fn preload_languages(&self, languages: &[&str]) -> Result<()> {
+		languages.iter().try_for_each(|language| self.preload(language))
+	}
+
+	/// Gets a lazily loaded language, loading it if that was not done yet.
+	pub(crate) fn load_lazy<'a>(&self, lazy: &'a LazyLanguage) -> Result<&'a LoadedLanguage> {
+		if let Some(loaded) = lazy.loaded.get() {
+			return Ok(loaded);
+		}
+
+		let resources = [&self.shared, Self::read_resources(self.source.as_ref(), &lazy.id, &lazy.paths, &self.options)?.as_slice()].concat();
+		let (bundle, _) = self.options.build_bundle(&lazy.id, &resources)?;
+		let stats = LanguageStats::of(&resources);
+		Ok(lazy.loaded.get_or_init(|| LoadedLanguage { bundle, resources, stats }))
+	}
+
+	/// Gets the resources the bundle of the given language was built from, without loading it
+	/// if it is loaded lazily and was not used yet, in which case [None] is returned.
+	pub(crate) fn loaded_resources(&self, language: &str) -> Option<&[LoadedResource]> {
+		match self.lazy.get(language) {
+			Some(lazy) => lazy.loaded.get().map(|loaded| loaded.resources.as_slice()),
+			None => self.resources.get(language).map(Vec::as_slice)
+		}
+	}
+
+	/// Gets the resources the bundle of the given language was built from, loading it if it is
+	/// loaded lazily. Languages that are not available, or that fail to load, give [None].
+	pub(crate) fn language_resources(&self, language: &str) -> Option<&[LoadedResource]> {
+		match self.lazy.get(language) {
+			Some(lazy) => self.load_lazy(lazy).ok().map(|loaded| loaded.resources.as_slice()),
+			None => self.resources.get(language).map(Vec::as_slice)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{error::Error, InMemory, LocaliserBuilder};
+
+	fn source() -> InMemory {
+		InMemory::new()
+			.with_resource("en-US", "hello = Hello!")
+			.with_resource("de", "hello = Hallo!")
+			.with_resource("fr", "hello = Bonjour !")
+			.with_resource("it", "hello = Ciao!\n= broken")
+	}
+
+	#[test]
+	fn defers_loading() {
+		assert!(matches!(LocaliserBuilder::with_source(source(), "en-US").build(), Err(Error::LoadErrors(errors)) if errors.failures[0].language == "it"));
+
+		let loc = LocaliserBuilder::with_source(source(), "en-US").lazy(true).build().unwrap();
+		assert!(loc.loaded_resources("en-US").is_some());
+		assert!(loc.loaded_resources("de").is_none());
+		assert!(loc.loaded_resources("it").is_none());
+
+		assert_eq!(loc.get_message("hello", "de", None).unwrap(), "Hallo!");
+		assert!(loc.loaded_resources("de").is_some());
+		assert!(loc.loaded_resources("fr").is_none());
+
+		// failures are not remembered, so each use tries again
+		assert!(matches!(loc.get_message("hello", "it", None), Err(Error::ParseError { .. })));
+		assert!(matches!(loc.get_message("hello", "it", None), Err(Error::ParseError { .. })));
+		assert!(loc.loaded_resources("it").is_none());
+	}
+
+	#[test]
+	fn preloads_languages() {
+		let loc = LocaliserBuilder::with_source(source(), "en-US").lazy(true).build().unwrap();
+		loc.preload_languages(&["DE", "fr"]).unwrap();
+		assert!(loc.loaded_resources("de").is_some());
+		assert!(loc.loaded_resources("fr").is_some());
+		assert!(loc.loaded_resources("it").is_none());
+		loc.preload_languages(&["de", "en-US"]).unwrap();
+
+		let loc = LocaliserBuilder::with_source(source(), "en-US").lazy(true).build().unwrap();
+		assert!(matches!(loc.preload_languages(&["de", "it", "fr"]), Err(Error::ParseError { .. })));
+		assert!(loc.loaded_resources("de").is_some());
+		assert!(loc.loaded_resources("fr").is_none());
+		assert!(matches!(loc.preload("ja"), Err(Error::MissingLanguageError(lang)) if lang == "ja"));
+	}
+}
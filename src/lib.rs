@@ -3,136 +3,1429 @@
 //!
 //! Sample usage:
 //!
-//! ```rust
-//! let loc = fluent_fluently::Localiser::try_load("./locale".to_string(), "en-US".to_string()).unwrap();
-//! let msg = loc.get_message("hello-world", loc.available_languages.get("it"));
+//! ```rust,no_run
+//! # #[cfg(feature = "fs")] {
+//! let loc = fluent_fluently::Localiser::try_load("./locale", "en-US").unwrap();
+//! let msg = loc.get_message("hello-world", "it", None).unwrap();
 //! println!("{}", msg);
+//! # }
+//! ```
+//!
+//! Loading from the filesystem is behind the `fs` feature, enabled by default. Without it, the
+//! crate does not touch [std::fs] at all and builds for targets such as `wasm32-unknown-unknown`:
+//! localisations can then be loaded through [`Localiser::try_load_from()`], for instance from an
+//! [InMemory] source. The other features don't need the filesystem either, so this builds:
+//!
+//! ```text
+//! cargo build --target wasm32-unknown-unknown --no-default-features --features gettext,json-import,display-names
 //! ```
 //!
 //! The [FluentMessage] you obtained this way will automatically fall back on `en-US` if no locale
-//! of the requested type was found. Though, if you want, you `bundles` is a [HashMap], so you can
-//! certainly check whether a language is available manually if you so wish.
+//! of the requested type was found. Though, if you want, you can certainly check whether a
+//! language is available manually through [`Localiser::available_languages()`].
+//!
+//! ## Migrating from public fields
+//! The `bundles` and `available_languages` fields of [Localiser] are no longer public, so that
+//! it can't be left in an inconsistent state. Use these instead:
+//!  * `loc.bundles.get(lang)` → [`Localiser::language_bundle()`], or
+//!    [`Localiser::default_bundle()`] for the default language;
+//!  * `loc.available_languages.values()` → [`Localiser::available_languages()`];
+//!  * inserting into `loc.bundles` → [`Localiser::insert_from_reader()`] or
+//!    [`Localiser::merge()`].
 
-use std::{collections::HashMap, sync::Arc};
-use fluent::{bundle::FluentBundle, FluentResource, FluentArgs};
+use std::{collections::{BTreeMap, BTreeSet, HashMap, HashSet}, fmt, path::{Path, PathBuf}, result::Result as StdResult, sync::Arc};
+use fluent::{bundle::FluentBundle, FluentResource};
+use fluent_syntax::ast;
 use intl_memoizer::concurrent::IntlLangMemoizer;
 use unic_langid::LanguageIdentifier;
-use crate::error::Result;
+use crate::{builder::Options, error::{LoadFailure, Result}, lazy::LazyLanguage, source::LanguageEntry};
 
+pub mod args;
+pub mod builder;
+mod cache;
+pub mod catalog;
+mod convert;
+pub mod coverage;
+#[cfg(feature = "display-names")]
+mod display_names;
+pub mod duplicate;
+pub mod encoding;
 pub mod error;
+pub mod events;
+pub mod export;
+mod fallback;
+#[cfg(feature = "gettext")]
+pub mod gettext;
+pub mod iter;
+#[cfg(feature = "json-import")]
+pub mod json;
+pub mod key;
+mod lazy;
+pub mod lint;
+pub mod manifest;
+pub mod merge;
+mod modify;
+mod namespace;
+pub mod number;
+pub mod request;
+pub mod scoped;
+pub mod shared;
+pub mod source;
+pub mod stats;
+pub mod stub;
+pub mod validate;
+mod macros;
+
+pub use fluent::{FluentArgs, FluentValue};
+pub use fluent_args as args;
+pub use args::ToFluentArgs;
+#[cfg(feature = "derive")]
+pub use fluent_fluently_derive::{messages, ToFluentArgs};
+pub use builder::LocaliserBuilder;
+pub use catalog::{Catalog, ExportData};
+pub use coverage::CoverageReport;
+pub use duplicate::DuplicatePolicy;
+pub use encoding::Encoding;
+pub use events::ReloadEvent;
+pub use export::ExportFormat;
+pub use key::MessageKey;
+pub use lint::LintReport;
+pub use merge::{MergePolicy, MergeReport};
+pub use request::MessageRequest;
+pub use scoped::ScopedLocaliser;
+pub use shared::SharedLocaliser;
+pub use source::{InMemory, ResourceSource};
+#[cfg(feature = "fs")]
+pub use source::FileSystem;
+pub use stats::Stats;
 
 /// Shorthand type handling the [FluentBundle]'s generic types.
-type TypedFluentBundle = FluentBundle<Arc<FluentResource>, IntlLangMemoizer>;
+pub type TypedFluentBundle = FluentBundle<Arc<FluentResource>, IntlLangMemoizer>;
+
+/// A parsed [FluentResource], along with the identifier of the resource it was read from.
+#[derive(Clone)]
+pub(crate) struct LoadedResource {
+	/// The identifier the resource was read from, usually its path.
+	pub(crate) path: PathBuf,
+	/// The parsed resource.
+	pub(crate) resource: Arc<FluentResource>
+}
 
 /// The main struct of the program.
 /// You can obtain a new instance by calling [`Self::try_load()`].
+///
+/// Everything is kept behind an [Arc], so cloning is cheap: clones share all of their state
+/// with the original, and no file is read or parsed again. This makes it easy to hand a copy to
+/// each thread or task. Changes made to a clone are copy-on-write, and never affect the others:
+/// internal maps are only copied when a clone that shares them is modified.
+#[derive(Clone)]
 pub struct Localiser {
-	/// A [HashMap] tying each bundle to its language identifier.
-	pub bundles: HashMap<String, TypedFluentBundle>,
+	/// A [HashMap] tying each bundle to its language identifier. Languages loaded lazily are
+	/// not included, see [`LocaliserBuilder::lazy()`].
+	bundles: Arc<HashMap<String, Arc<TypedFluentBundle>>>,
 	/// A [HashMap] tying each *available* language identifier [String] to an actual [LanguageIdentifier].
-	pub available_languages: HashMap<String, LanguageIdentifier>,
+	available_languages: Arc<HashMap<String, LanguageIdentifier>>,
 	/// The identifier of the default language.
-	pub default_language: String
+	default_language: Arc<str>,
+	/// A [HashMap] tying each language identifier to the [FluentResource]s its bundle was built
+	/// from, kept around to inspect their contents.
+	resources: Arc<HashMap<String, Vec<LoadedResource>>>,
+	/// A [HashMap] tying each language identifier to how much content it holds, counted once
+	/// when the language is loaded. Languages loaded lazily are not included.
+	language_stats: Arc<HashMap<String, stats::LanguageStats>>,
+	/// The resources added to the bundle of every language before its own, see
+	/// [`LocaliserBuilder::shared()`].
+	shared: Arc<[LoadedResource]>,
+	/// The languages that are only loaded when first needed, by language identifier.
+	lazy: Arc<HashMap<String, Arc<LazyLanguage>>>,
+	/// The source the resources were read from.
+	source: Arc<dyn ResourceSource>,
+	/// The settings this was built with.
+	options: Arc<Options>,
+	/// The duplicate definitions found while loading, if they were requested to be tracked.
+	duplicates: Arc<[duplicate::Duplicate]>,
+	/// The files of the languages left out while loading, if translations were allowed to fail.
+	load_failures: Arc<[Arc<LoadFailure>]>,
+	/// The resources the source left out while loading, because of the patterns it was given.
+	excluded: Arc<[PathBuf]>,
+	/// The channels to notify whenever the loaded data changes.
+	subscribers: events::Subscribers,
+	/// The formatted values of messages without arguments, if caching them was requested.
+	cache: Arc<cache::MessageCache>
 }
 
 impl Localiser {
 	/// Tries to create a new [Localiser] instance given a path and the name of the default language.
-	/// The path's direct children will only be considered if their names are valid language codes as
-	/// defined by [LanguageIdentifier], and if they are either files with the `.ftl` extension or
-	/// directories. In the first case they will be read directly and converted in [FluentResource]s,
+	/// The path is read through [FileSystem]: its direct children will only be considered if their
+	/// names are valid language codes as defined by [LanguageIdentifier], and if they are either
+	/// files with the `.ftl` extension or directories. In the first case they will be read directly and converted in [FluentResource]s,
 	/// in the second case the same will be done to their chilren instead.
 	/// [FluentResource]s within a same folder will be considered part of a same [FluentBundle],
 	/// forming a single localisation for all intents and purposes.
-	pub fn try_load(path: &str, default_language: &str) -> Result<Self> {
+	/// If nothing was found for the default language as it was given, the closest language found
+	/// is used instead, as described in [`Self::resolve_language()`]: `en` becomes `en-US` if
+	/// that is all there is, and [`Self::default_language()`] returns the latter.
+	/// Fails with [`error::Error::NoLanguagesLoaded`] if no language at all was found, with
+	/// [`error::Error::DefaultLanguageMissing`] if nothing matched the default language even
+	/// loosely, and with [`error::Error::LoadErrors`] listing every file that could not be loaded, if any.
+	///
+	/// The path may be anything that can be seen as a [Path], including [PathBuf]s that are not
+	/// valid UTF-8. See [`FileSystem::skipped()`] for what happens to such names inside it.
+	#[cfg(feature = "fs")]
+	pub fn try_load(path: impl AsRef<Path>, default_language: &str) -> Result<Self> {
+		Self::builder(path, default_language).build()
+	}
+
+	/// Creates a [LocaliserBuilder], which allows further customisation of the loading process.
+	#[cfg(feature = "fs")]
+	pub fn builder(path: impl AsRef<Path>, default_language: &str) -> LocaliserBuilder {
+		LocaliserBuilder::new(path, default_language)
+	}
+
+	/// Tries to create a new [Localiser] instance, reading from any [ResourceSource] rather than
+	/// from the filesystem. Languages are validated and put together as described in
+	/// [`Self::try_load()`].
+	pub fn try_load_from(source: impl ResourceSource + 'static, default_language: &str) -> Result<Self> {
+		LocaliserBuilder::with_source(source, default_language).build()
+	}
+
+	/// Wraps bundles that were built elsewhere, for instance with custom functions, tied to the
+	/// codes of their languages, which must be valid [LanguageIdentifier]s. Fails with
+	/// [`error::Error::DefaultLanguageMissing`] if there is no bundle for the default language.
+	///
+	/// Since the resources the bundles were built from are not known, everything that inspects
+	/// them, such as [`Self::message_keys()`], [`Self::stats()`] or [`Self::check_coverage()`],
+	/// acts as if the bundles were empty, and there is nothing to [reload](Self::reload()) from.
+	/// [`Self::merge()`] fails with [`error::Error::UnknownResources`] instead, since it would
+	/// have to rebuild the bundles.
+	/// Looking up messages works as usual.
+	///
+	/// ```rust
+	/// use std::{collections::HashMap, sync::Arc};
+	/// use fluent_fluently::{FluentValue, Localiser, TypedFluentBundle};
+	/// use fluent::FluentResource;
+	///
+	/// let mut bundle = TypedFluentBundle::new_concurrent(vec!["en-US".parse().unwrap()]);
+	/// bundle.add_function("SHOUT", |args, _| match args.first() {
+	///     Some(FluentValue::String(s)) => FluentValue::from(s.to_uppercase()),
+	///     _ => FluentValue::Error
+	/// }).unwrap();
+	/// bundle.add_resource(Arc::new(FluentResource::try_new("hello = { SHOUT(\"hello\") }!".to_string()).unwrap())).unwrap();
+	///
+	/// let loc = Localiser::from_bundles(HashMap::from([("en-US".to_string(), bundle)]), "en-US").unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "\u{2068}HELLO\u{2069}!");
+	/// ```
+	pub fn from_bundles(bundles: HashMap<String, TypedFluentBundle>, default_language: &str) -> Result<Self> {
+		let default_language = fallback::parse_code(default_language)?.0;
+		let mut available_languages = HashMap::new();
+		let mut loaded = HashMap::new();
+		for (language, bundle) in bundles {
+			let (code, id) = fallback::parse_code(&language)?;
+			loaded.insert(code.clone(), Arc::new(bundle));
+			available_languages.insert(code, id);
+		}
+
+		if !loaded.contains_key(&default_language) {
+			return Err(error::Error::DefaultLanguageMissing(default_language));
+		}
+
+		Ok(Self {
+			bundles: Arc::new(loaded),
+			available_languages: Arc::new(available_languages),
+			default_language: default_language.into(),
+			resources: Arc::default(),
+			language_stats: Arc::default(),
+			shared: Arc::new([]),
+			lazy: Arc::default(),
+			source: Arc::new(InMemory::new()),
+			options: Arc::default(),
+			duplicates: Arc::new([]),
+			load_failures: Arc::new([]),
+			excluded: Arc::new([]),
+			subscribers: events::Subscribers::default(),
+			cache: Arc::default()
+		})
+	}
+
+	/// Does the actual loading for [`Self::try_load()`] and [LocaliserBuilder].
+	pub(crate) fn load(source: Arc<dyn ResourceSource>, default_language: &str, mut options: Options) -> Result<Self> {
 		let mut bundles = HashMap::new();
+		let mut resources = HashMap::new();
 		let mut available_languages = HashMap::new();
-		let paths = std::fs::read_dir(path)?
-			.filter_map(|res| res.ok())
-			.map(|dir_entry| dir_entry.path())
-			.filter_map(|path| {
-				if path.extension().map_or(false, |ext| ext == "ftl") || path.is_dir() {
-					Some(path)
-				} else {
-					None
-				}
-			}).collect::<Vec<_>>();
+		let mut lazy = HashMap::new();
+		let mut duplicates = Vec::new();
 
 		// validate default
-		let default_language = default_language.parse::<LanguageIdentifier>()?.to_string();
+		let mut default_language = fallback::parse_code(default_language)?.0;
 
-		for path in paths {
-			// validate filename as language code
-			let language_code = path.file_stem()
-				.and_then(|f| f.to_str())
-				.map(|f| f.parse::<LanguageIdentifier>())
-				.and_then(|id| match id {
-					Ok(id) => Some(id),
-					Err(_) => None
-				});
+		let mut failures = Vec::new();
+		let mut lenient_failures = source.skipped()?.into_iter()
+			.map(|path| LoadFailure {
+				language: path.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+				error: error::Error::InvalidFileName { path: path.clone() },
+				path
+			}).collect::<Vec<_>>();
+		let excluded = source.excluded()?;
+		let (shared_entries, mut entries) = source.languages()?.into_iter()
+			.partition::<Vec<_>, _>(|entry| options.shared.contains(&entry.name));
+		options.manifest = source.manifest()?;
+		if let Some(manifest) = &options.manifest {
+			let declared = |entry: &LanguageEntry| manifest.language(&entry.name).is_some_and(|lang| lang.enabled);
+			entries.retain(declared);
+		}
 
-			if language_code.is_none() {
-				continue;
+		// settle for the closest match if the default language was not found as it was given
+		let found = entries.iter()
+			.filter_map(|entry| fallback::parse_code(&entry.name).ok())
+			.collect::<HashMap<_, _>>();
+		if let Some(code) = fallback::negotiate(&default_language, &found) {
+			default_language = code.to_string();
+		}
+
+		if let Some(manifest) = &options.manifest {
+			for lang in manifest.languages().iter().filter(|lang| lang.enabled) {
+				if !entries.iter().any(|entry| manifest.language(&entry.name) == Some(lang)) {
+					let failure = LoadFailure {
+						language: lang.code.clone(),
+						path: PathBuf::from(manifest::MANIFEST_NAME),
+						error: error::Error::MissingLanguageError(lang.code.clone())
+					};
+					match options.lenient_translations && lang.code != default_language {
+						true => lenient_failures.push(failure),
+						false => failures.push(failure)
+					}
+				}
 			}
+		}
+		let mut shared = Vec::new();
+		for entry in shared_entries {
+			match Self::read_resources(source.as_ref(), &LanguageIdentifier::default(), &entry.resources, &options) {
+				Ok(entry_resources) => shared.extend(entry_resources),
+				Err(entry_failures) => failures.extend(entry_failures.into_iter()
+					.map(|failure| LoadFailure { language: entry.name.clone(), ..failure }))
+			}
+		}
+
+		for entry in entries {
+			// validate name as language code
+			let Ok((code, language_code)) = fallback::parse_code(&entry.name) else {
+				continue;
+			};
 
-			let language_code = language_code.unwrap();
+			if options.lazy && code != default_language {
+				available_languages.insert(code.clone(), language_code.clone());
+				lazy.insert(code, Arc::new(LazyLanguage::new(language_code, entry.resources)));
+				continue;
+			}
 
-			let mut bundle: TypedFluentBundle = fluent::bundle::FluentBundle::new_concurrent(vec![language_code.clone()]);
-			if path.is_dir() { //is a directory
-				for res in Self::path_to_resources(&path)? {
-					bundle.add_resource(res)?;
+			// keep going after a failure, to report every broken file at once
+			let built = Self::read_resources(source.as_ref(), &language_code, &entry.resources, &options)
+				.map(|language_resources| [shared.as_slice(), &language_resources].concat())
+				.and_then(|language_resources| options.build_bundle(&language_code, &language_resources)
+					.map(|built| (built, language_resources)));
+			let ((bundle, language_duplicates), language_resources) = match built {
+				Ok(built) => built,
+				Err(language_failures) => {
+					let language_failures = language_failures.into_iter()
+						.map(|failure| LoadFailure { language: code.clone(), ..failure });
+					match options.lenient_translations && code != default_language {
+						true => lenient_failures.extend(language_failures),
+						false => failures.extend(language_failures)
+					}
+					continue;
 				}
-			} else { //is a single file
-				bundle.add_resource(Self::file_to_resource(&path)?)?;
+			};
+
+			duplicates.extend(language_duplicates);
+			available_languages.insert(code.clone(), language_code);
+			bundles.insert(code.clone(), Arc::new(bundle));
+			resources.insert(code, language_resources);
+		}
+
+		if available_languages.is_empty() && failures.is_empty() {
+			return Err(error::Error::NoLanguagesLoaded);
+		}
+
+		if !bundles.contains_key(&default_language) && failures.is_empty() {
+			return Err(error::Error::DefaultLanguageMissing(default_language));
+		}
+
+		let has_default = bundles.contains_key(&default_language);
+		let localiser = Self {
+			bundles: Arc::new(bundles),
+			available_languages: Arc::new(available_languages),
+			default_language: default_language.into(),
+			language_stats: Arc::new(resources.iter()
+				.map(|(lang, resources)| (lang.clone(), stats::LanguageStats::of(resources)))
+				.collect()),
+			resources: Arc::new(resources),
+			shared: shared.into(),
+			lazy: Arc::new(lazy),
+			source,
+			options: Arc::new(options),
+			duplicates: duplicates.into(),
+			load_failures: Arc::new([]),
+			excluded: excluded.into(),
+			subscribers: events::Subscribers::default(),
+			cache: Arc::default()
+		};
+
+		if !failures.is_empty() {
+			failures.extend(lenient_failures);
+			return Err(error::Error::LoadErrors(error::LoadErrors {
+				failures,
+				partial: has_default.then(|| Box::new(localiser))
+			}));
+		}
+
+		let localiser = Self { load_failures: lenient_failures.into_iter().map(Arc::new).collect(), ..localiser };
+
+		if localiser.options.validate_references {
+			let broken = localiser.validate_references();
+			if !broken.is_empty() {
+				return Err(error::Error::BrokenReferences(broken));
 			}
+		}
+
+		Ok(localiser)
+	}
+
+	/// Returns the identifier of the default language, which may be more specific than the one
+	/// that was requested when loading.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello!"), "en").unwrap();
+	/// assert_eq!(loc.default_language(), "en-US");
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Hello!");
+	/// assert!(Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello!"), "it").is_err());
+	/// ```
+	pub fn default_language(&self) -> &str {
+		&self.default_language
+	}
+
+	/// Iterates over the identifiers of the available languages, in no particular order,
+	/// including those loaded lazily that were not used yet.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!");
+	/// let loc = Localiser::try_load_from(source, "en-US").unwrap();
+	/// let mut languages = loc.available_languages().map(|id| id.to_string()).collect::<Vec<_>>();
+	/// languages.sort();
+	/// assert_eq!(languages, ["en-US", "it"]);
+	/// ```
+	pub fn available_languages(&self) -> impl Iterator<Item = &LanguageIdentifier> {
+		self.available_languages.values()
+	}
 
-			bundles.insert(language_code.to_string(), bundle);
-			available_languages.insert(language_code.to_string(), language_code);
+	/// Gets the bundle of exactly the given language, without falling back on any other, loading
+	/// it if it is loaded lazily. Gives [None] if the language is not available or fails to load.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!"), "en-US").unwrap();
+	/// assert!(loc.language_bundle("it").is_some_and(|bundle| bundle.has_message("hello")));
+	/// assert!(loc.language_bundle("it-IT").is_none());
+	/// assert!(loc.default_bundle().has_message("hello"));
+	/// ```
+	pub fn language_bundle(&self, language: &str) -> Option<&TypedFluentBundle> {
+		let language = fallback::parse_code(language).ok()?.0;
+		if !self.available_languages.contains_key(&language) {
+			return None;
 		}
 
-		Ok(Self {
-			bundles,
-			available_languages,
-			default_language
-		})
+		self.loaded_bundle(&language).ok()
 	}
 
-	/// Reads all files in a certain folder and all of its subfolders that have the `.ftl`
-	/// extension, parses them into [FluentResource]s and returns them in a [Vec]. 
-	fn path_to_resources(path: &std::path::PathBuf) -> Result<Vec<Arc<FluentResource>>> {
-		let mut res = Vec::new();
-		for entry in walkdir::WalkDir::new(path).follow_links(true).into_iter().filter_map(|e| e.ok()) {
-			let entry_path = entry.path().to_path_buf();
-			let entry_extension = entry_path.extension();
-			if entry_extension.is_none() || entry_extension.unwrap() != "ftl" {
-				continue;
+	/// Gets the bundle of the default language, which is always loaded.
+	pub fn default_bundle(&self) -> &TypedFluentBundle {
+		self.loaded_bundle(&self.default_language)
+			.expect("the default language is always loaded")
+	}
+
+	/// Loads everything again from the source this was loaded from, with the same settings,
+	/// picking up any change made to the files in the meantime. The current default language is
+	/// kept. The new state only replaces the current one once loading succeeded: on failure,
+	/// such as a file that can no longer be parsed, nothing changes and the error is returned.
+	/// Clones made before this keep the old state. Subscribers are sent a [ReloadEvent] listing
+	/// every language that was available before or after.
+	pub fn reload(&mut self) -> Result<()> {
+		let mut reloaded = Self::load(self.source.clone(), &self.default_language, Options::clone(&self.options))?;
+		reloaded.subscribers = std::mem::take(&mut self.subscribers);
+		let mut languages = self.available_languages.keys()
+			.chain(reloaded.available_languages.keys())
+			.cloned()
+			.collect::<Vec<_>>();
+		languages.sort();
+		languages.dedup();
+
+		*self = reloaded;
+		self.subscribers.notify(ReloadEvent { languages });
+		Ok(())
+	}
+
+	/// Changes the default language, which is used as fallback by [`Self::get_message()`].
+	/// The given code is validated as a [LanguageIdentifier], and a bundle for it must have been
+	/// loaded already.
+	pub fn set_default_language(&mut self, language: &str) -> Result<()> {
+		let language = fallback::parse_code(language)?.0;
+		if !self.available_languages.contains_key(&language) {
+			return Err(error::Error::MissingLanguageError(language));
+		}
+
+		self.preload(&language)?;
+		self.default_language = language.into();
+		Ok(())
+	}
+
+	/// Returns a [ScopedLocaliser] bound to the given language, so that it doesn't need to be
+	/// passed on every call. The language is resolved once, as described in
+	/// [`Self::resolve_language()`].
+	pub fn scoped(&self, language: &str) -> ScopedLocaliser<'_> {
+		ScopedLocaliser::new(self, language)
+	}
+
+	/// Lists the identifiers of all messages defined for the given language, in the order they
+	/// were loaded in.
+	pub fn message_keys(&self, language: &str) -> Result<Vec<String>> {
+		if self.language_resources(language).is_none() {
+			return Err(error::Error::MissingLanguageError(language.to_string()));
+		}
+
+		Ok(self.message_entries(language)
+			.map(|msg| self.public_key(msg.id.name))
+			.collect())
+	}
+
+	/// Lists the identifiers of all terms defined for the given language, without the leading
+	/// `-`, in the order they were loaded in.
+	pub fn term_keys(&self, language: &str) -> Result<Vec<String>> {
+		if self.language_resources(language).is_none() {
+			return Err(error::Error::MissingLanguageError(language.to_string()));
+		}
+
+		Ok(self.term_entries(language)
+			.map(|term| term.id.name.to_string())
+			.collect())
+	}
+
+	/// Iterates over the AST of every entry found in the resources of the given language.
+	pub(crate) fn entries(&self, language: &str) -> impl Iterator<Item = &ast::Entry<&str>> {
+		self.language_resources(language)
+			.into_iter()
+			.flatten()
+			.flat_map(|res| res.resource.entries())
+	}
+
+	/// Iterates over the AST of every message defined for the given language. If a message was
+	/// defined more than once, only the definition in use is considered, in the position of the
+	/// first one.
+	pub(crate) fn message_entries(&self, language: &str) -> impl Iterator<Item = &ast::Message<&str>> {
+		Self::deduplicate(self.entries(language).filter_map(|entry| match entry {
+			ast::Entry::Message(msg) => Some((msg.id.name, msg)),
+			_ => None
+		}))
+	}
+
+	/// Iterates over the AST of every term defined for the given language, handling duplicates
+	/// like [`Self::message_entries()`].
+	pub(crate) fn term_entries(&self, language: &str) -> impl Iterator<Item = &ast::Term<&str>> {
+		Self::deduplicate(self.entries(language).filter_map(|entry| match entry {
+			ast::Entry::Term(term) => Some((term.id.name, term)),
+			_ => None
+		}))
+	}
+
+	/// Keeps only the last value found for each identifier, in the position of the first one.
+	fn deduplicate<'a, T: 'a>(entries: impl Iterator<Item = (&'a str, T)>) -> impl Iterator<Item = T> + 'a {
+		let mut order = Vec::new();
+		let mut latest = HashMap::new();
+		for (id, entry) in entries {
+			if latest.insert(id, entry).is_none() {
+				order.push(id);
 			}
+		}
+		order.into_iter().filter_map(move |id| latest.remove(id))
+	}
+
+	/// Finds the AST of a message defined for the given language.
+	pub(crate) fn message_entry(&self, language: &str, key: &str) -> Option<&ast::Message<&str>> {
+		let key = self.internal_key(key);
+		self.message_entries(language).find(|msg| msg.id.name == key)
+	}
+
+	/// Returns the comment attached to a message in the given language, if any. Multiline
+	/// comments are joined with newlines, without the leading `#`. Group (`##`) and resource
+	/// (`###`) comments are not attached to any message, so they are never returned.
+	/// Since [FluentResource] discards comments when parsing, this parses the resource defining
+	/// the message again every time it's called.
+	pub fn comment(&self, key: &str, language: &str) -> Option<String> {
+		let key = self.internal_key(key);
+		let defines = |entry: &ast::Entry<&str>| matches!(entry, ast::Entry::Message(msg) if msg.id.name == key);
+		let res = self.language_resources(language)?
+			.iter()
+			.rfind(|res| res.resource.entries().any(defines))?;
+		fluent_syntax::parser::parse(res.resource.source())
+			.unwrap_or_else(|(partial, _)| partial)
+			.body
+			.into_iter()
+			.rfind(defines)
+			.and_then(|entry| match entry {
+				ast::Entry::Message(msg) => msg.comment,
+				_ => None
+			})
+			.map(|comment| comment.content.join("\n"))
+	}
+
+	/// Returns the FTL source text of each resource of the given language, in the order they were
+	/// loaded in, or nothing if the language is not available. [FluentResource] keeps its source
+	/// around anyway, so this costs no extra memory. Resources converted from other formats give
+	/// the FTL they were converted into.
+	pub fn sources(&self, language: &str) -> Vec<&str> {
+		self.language_resources(language)
+			.into_iter()
+			.flatten()
+			.map(|res| res.resource.source())
+			.collect()
+	}
+
+	/// Ties each message defined for the given language to the resource it was read from, which
+	/// is the path of its file when loading from the filesystem. If a message was defined more
+	/// than once, the resource of the definition in use is given. Messages are sorted by
+	/// identifier.
+	pub fn message_index(&self, language: &str) -> Result<BTreeMap<String, PathBuf>> {
+		let resources = self.language_resources(language)
+			.ok_or_else(|| error::Error::MissingLanguageError(language.to_string()))?;
+
+		Ok(resources.iter()
+			.flat_map(|res| res.resource.entries().filter_map(move |entry| match entry {
+				ast::Entry::Message(msg) => Some((self.public_key(msg.id.name), res.path.clone())),
+				_ => None
+			}))
+			.collect())
+	}
+
+	/// Lists the messages and terms that were defined more than once within the same language,
+	/// if requested through [`LocaliserBuilder::on_duplicate()`]. Languages loaded lazily are not
+	/// included. Those of a language replaced at runtime, such as through
+	/// [`Self::replace_language()`], are replaced by the ones found in its new resources.
+	pub fn duplicates(&self) -> &[duplicate::Duplicate] {
+		&self.duplicates
+	}
 
-			res.push(Self::file_to_resource(&entry_path)?);
+	/// Lists the files that could not be loaded, if
+	/// [`LocaliserBuilder::lenient_translations()`] let loading go on without the languages they
+	/// belong to, along with the entries of the source that were left out because their names
+	/// are not valid UTF-8, see [`ResourceSource::skipped()`]. The failures of a language that
+	/// is replaced or removed at runtime are forgotten.
+	pub fn load_failures(&self) -> &[Arc<LoadFailure>] {
+		&self.load_failures
+	}
+
+	/// Lists the resources that were left out while loading because of the patterns given to
+	/// [`LocaliserBuilder::include_glob()`] and [`LocaliserBuilder::exclude_glob()`], as reported
+	/// by [`ResourceSource::excluded()`].
+	pub fn excluded_resources(&self) -> &[PathBuf] {
+		&self.excluded
+	}
+
+	/// Counts the loaded languages and what each of them holds. See [Stats]. The counts are
+	/// taken once when each language is loaded, so this is cheap to call repeatedly, but it
+	/// loads languages that are loaded lazily.
+	///
+	/// ```rust
+	/// use fluent_fluently::{stats::LanguageStats, InMemory, Localiser};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "-brand = Fluently\n    .gender = neuter\nhello = Hello!")
+	///     .with_resource("en-US", "login = Log in\n    .title = Log in to { -brand }\n    .accesskey = L")
+	///     .with_resource("it", "hello = Ciao!");
+	/// let stats = Localiser::try_load_from(source, "en-US").unwrap().stats();
+	/// assert_eq!(stats.per_language["en-US"], LanguageStats { messages: 2, terms: 1, attributes: 3, files: 2 });
+	/// assert_eq!(stats.per_language["it"], LanguageStats { messages: 1, terms: 0, attributes: 0, files: 1 });
+	/// assert_eq!(stats.totals, LanguageStats { messages: 3, terms: 1, attributes: 3, files: 3 });
+	/// assert_eq!(stats.total_messages, 3);
+	/// ```
+	pub fn stats(&self) -> Stats {
+		let per_language = self.available_languages.keys()
+			.map(|lang| (lang.clone(), self.language_stats(lang).unwrap_or_default()))
+			.collect::<HashMap<_, _>>();
+
+		Stats {
+			languages: self.available_languages.len(),
+			total_messages: per_language.values().map(|stats| stats.messages).sum(),
+			messages_per_language: per_language.iter()
+				.map(|(lang, stats)| (lang.clone(), stats.messages))
+				.collect(),
+			totals: per_language.values().copied().fold(stats::LanguageStats::default(), |a, b| a + b),
+			per_language
+		}
+	}
+
+	/// Gets how much content the given language holds, loading it if it is loaded lazily.
+	/// Languages that are not available, or that fail to load, give [None].
+	fn language_stats(&self, language: &str) -> Option<stats::LanguageStats> {
+		match self.lazy.get(language) {
+			Some(lazy) => self.load_lazy(lazy).ok().map(|loaded| loaded.stats),
+			None => self.language_stats.get(language).copied()
+		}
+	}
+
+	/// Reads and parses the resources making up a language, in order. Every resource is tried,
+	/// and all those that failed are reported.
+	pub(crate) fn read_resources(source: &dyn ResourceSource, language: &LanguageIdentifier, paths: &[PathBuf], options: &Options) -> StdResult<Vec<LoadedResource>, Vec<LoadFailure>> {
+		let mut resources = Vec::new();
+		let mut failures = Vec::new();
+		for path in paths {
+			let resource = Self::read_resource(source, path)
+				.and_then(|bytes| Self::bytes_to_resource(path, bytes, language, options))
+				.and_then(|resource| match source.namespace(path).filter(|_| options.namespace_separator.is_some()) {
+					Some(namespace) => namespace::namespace_resource(resource, &namespace, options),
+					None => Ok(resource)
+				});
+			match resource {
+				Ok(resource) => resources.push(LoadedResource { path: path.clone(), resource: Arc::new(resource) }),
+				Err(error) => failures.push(LoadFailure { language: language.to_string(), path: path.clone(), error })
+			}
+		}
+
+		if failures.is_empty() {
+			Ok(resources)
+		} else {
+			Err(failures)
 		}
-		Ok(res)
 	}
 
-	/// Reads the file at the given path, and tries to parse it into a [FluentResource].
-	fn file_to_resource(path: &std::path::PathBuf) -> Result<Arc<FluentResource>> {
-		Ok(Arc::new(FluentResource::try_new(std::fs::read_to_string(path)?)?))
+	/// Reads a resource from the given source, adding its path to I/O errors.
+	fn read_resource(source: &dyn ResourceSource, path: &Path) -> Result<Vec<u8>> {
+		source.read(path).map_err(|err| match err {
+			error::Error::IoError(err) => error::Error::IoError(
+				std::io::Error::new(err.kind(), format!("Failed to read {}: {}", path.display(), err))
+			),
+			err => err
+		})
+	}
+
+	/// Tries to parse the raw contents of a file into a [FluentResource]. With the `gettext` and
+	/// `json-import` features, `.po` and `.json` files are converted to FTL first: positions in
+	/// parse errors then refer to the converted text.
+	#[cfg_attr(not(feature = "gettext"), allow(unused_variables))]
+	pub(crate) fn bytes_to_resource(path: &Path, bytes: Vec<u8>, language: &LanguageIdentifier, options: &Options) -> Result<FluentResource> {
+		let source = options.encoding.decode(bytes)
+			.map_err(|reason| error::Error::InvalidEncoding { path: path.to_path_buf(), reason })?;
+		let converted = match path.extension().and_then(|ext| ext.to_str()) {
+			#[cfg(feature = "gettext")]
+			Some("po") => Some(gettext::po_to_ftl(&source, language, options.gettext_keys)),
+			#[cfg(feature = "json-import")]
+			Some("json") => Some(json::json_to_ftl(&source, &options.json_separator)),
+			_ => None
+		};
+		let source = match converted {
+			Some(Err(error::Error::GenericError(reason))) =>
+				return Err(error::Error::GenericError(format!("{} ({})", reason, path.display()))),
+			Some(converted) => converted?,
+			None => source
+		};
+		FluentResource::try_new(source)
+			.map_err(|(res, errors)| error::Error::ParseError {
+				path: path.to_path_buf(),
+				errors: errors.into_iter()
+					.map(|err| error::AnnotatedParserError::new(err, res.source()))
+					.collect()
+			})
 	}
 
-	/// Extracts a message from the requested bundle, or from the default one if absent. 
+	/// Extracts a message from the requested bundle. If the requested language is not available,
+	/// less specific versions of it are tried before the default one: for instance, a request for
+	/// `it-IT` is served by `it` if that is loaded.
+	///
+	/// Languages may have private-use subtags, such as `en-x-pirate`: these are only used when
+	/// requested exactly, and messages they lack are taken from the language they are based on.
+	/// Other extensions, such as `-u-ca-buddhist`, are ignored.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!\nbye = Bye!")
+	///     .with_resource("en-x-pirate", "hello = Ahoy!"), "en-US").unwrap();
+	/// assert_eq!(loc.get_message("hello", "en-x-pirate", None).unwrap(), "Ahoy!");
+	/// assert_eq!(loc.get_message("bye", "en-X-Pirate", None).unwrap(), "Bye!");
+	/// assert_eq!(loc.get_message("hello", "en-GB", None).unwrap(), "Hello!");
+	/// assert_eq!(loc.get_message("hello", "en-u-ca-buddhist", None).unwrap(), "Hello!");
+	/// ```
 	pub fn get_message(&self, key: &str, language: &str, args: Option<&FluentArgs>) -> Result<String> {
-		let bundle = self.bundles.get(language)
-			.or_else(|| self.bundles.get(&self.default_language))
-			.ok_or(error::Error::GenericError("Failed to get default bundle! This is not supposed to happen!".to_string()))?;
+		self.format_message(key, &language, self.try_resolve_language(language)?, args)
+	}
+
+	/// Like [`Self::get_message()`], but takes an already parsed [LanguageIdentifier]. If the
+	/// language is available, nothing is allocated before formatting: no string is built or
+	/// parsed to find its bundle.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	/// use unic_langid::LanguageIdentifier;
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!");
+	/// let loc = Localiser::try_load_from(source, "en-US").unwrap();
+	/// assert_eq!(loc.get_message_by_id("hello", &"it".parse::<LanguageIdentifier>().unwrap(), None).unwrap(), "Ciao!");
+	/// assert_eq!(loc.get_message_by_id("hello", &"it-IT".parse::<LanguageIdentifier>().unwrap(), None).unwrap(), "Ciao!");
+	/// ```
+	pub fn get_message_by_id(&self, key: &str, language: &LanguageIdentifier, args: Option<&FluentArgs>) -> Result<String> {
+		let resolved = match self.find_language_id(language) {
+			Some(resolved) => resolved,
+			None => self.try_resolve_language(&language.to_string())?
+		};
+		self.format_message(key, language, resolved, args)
+	}
+
+	/// Formats a message from the bundle of an already resolved language, using the cache if
+	/// that is enabled and there are no arguments. The requested language is only used for
+	/// errors.
+	fn format_message(&self, key: &str, requested: &dyn std::fmt::Display, resolved: &str, args: Option<&FluentArgs>) -> Result<String> {
+		let resolved = self.message_language(key, resolved);
+		let bundle = self.loaded_bundle(resolved)?;
+		if args.is_none() && self.options.cache_static_messages {
+			if let Some(cached) = self.cache.get(resolved, key) {
+				return Ok(cached.to_string());
+			}
+
+			let msg = self.format_pattern(bundle, self.message_value(bundle, key, requested)?, None, &key, resolved)?;
+			self.cache.insert(resolved, key, msg.as_str().into());
+			return Ok(msg);
+		}
+
+		let pattern = self.message_value(bundle, key, requested)?;
+		self.format_pattern(bundle, pattern, args, &key, resolved)
+	}
+
+	/// Like [`Self::get_message()`], but takes the arguments by value, so that they can be built
+	/// in a temporary without having to keep them alive separately.
+	///
+	/// ```rust
+	/// use fluent_fluently::{FluentArgs, InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello, { $name }!"), "en-US").unwrap();
+	/// let greet = |name: String| {
+	///     let mut args = FluentArgs::new();
+	///     args.set("name", name);
+	///     args
+	/// };
+	/// assert_eq!(loc.get_message_owned("hello", "en-US", greet("Anna".to_string())).unwrap(), "Hello, \u{2068}Anna\u{2069}!");
+	/// ```
+	pub fn get_message_owned(&self, key: &str, language: &str, args: FluentArgs<'static>) -> Result<String> {
+		self.get_message(key, language, Some(&args))
+	}
 
-		let pattern = bundle.get_message(key)
-			.and_then(|msg| msg.value())
-			.ok_or(error::Error::MissingMessageError(format!("No such message {} for language {}!", key, language)))?;
+	/// Like [`Self::get_message()`], but errors that happen while formatting, such as a missing
+	/// argument, don't make it fail: they are returned along with whatever Fluent managed to
+	/// produce regardless, where the parts that could not be formatted are replaced by markers
+	/// such as `{$name}`. Missing messages and languages are still errors, and so are mismatched
+	/// arguments if [`LocaliserBuilder::strict_args()`] was set.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello, { $name }!"), "en-US").unwrap();
+	/// assert!(loc.get_message("hello", "en-US", None).is_err());
+	///
+	/// let (msg, errors) = loc.get_message_partial("hello", "en-US", None).unwrap();
+	/// assert_eq!(msg, "Hello, \u{2068}{$name}\u{2069}!");
+	/// assert_eq!(errors.len(), 1);
+	/// ```
+	pub fn get_message_partial(&self, key: &str, language: &str, args: Option<&FluentArgs>) -> Result<(String, Vec<fluent::FluentError>)> {
+		let bundle = self.loaded_bundle(self.resolve_message_language(key, language)?)?;
+		let pattern = self.message_value(bundle, key, &language)?;
+
+		if self.options.strict_args {
+			Self::check_args(pattern, args)?;
+		}
 
 		let mut err = Vec::new();
 		let res = bundle.format_pattern(pattern, args, &mut err);
+		Ok((res.to_string(), err))
+	}
+
+	/// Like [`Self::get_message()`], but writes the message straight into the given sink instead
+	/// of allocating a new [String]. If formatting fails, whatever was formatted up to that point
+	/// may already have been written.
+	pub fn write_message<W: std::fmt::Write>(&self, out: &mut W, key: &str, language: &str, args: Option<&FluentArgs>) -> Result<()> {
+		let resolved = self.resolve_message_language(key, language)?;
+		let bundle = self.loaded_bundle(resolved)?;
+		let pattern = self.message_value(bundle, key, &language)?;
+
+		if self.options.strict_args {
+			Self::check_args(pattern, args)?;
+		}
+
+		let mut err = Vec::new();
+		bundle.write_pattern(out, pattern, args, &mut err)
+			.map_err(|_| error::Error::GenericError(format!("Failed to write message {}!", key)))?;
 		if err.is_empty() {
-			Ok(res.to_string())
+			Ok(())
 		} else {
-			Err(error::Error::FluentError(err))
+			Err(error::FormatError::wrap(err, &key, resolved))
 		}
 	}
+
+	/// Checks whether [`Self::get_message()`] would find a message with the given key for the
+	/// requested language, falling back the same way. Messages that only have attributes don't
+	/// count, see [`error::Error::MessageHasNoValue`].
+	pub fn has_message(&self, key: &str, language: &str) -> bool {
+		self.resolve_message_language(key, language)
+			.and_then(|resolved| self.loaded_bundle(resolved))
+			.is_ok_and(|bundle| bundle.get_message(&self.internal_key(key)).is_some_and(|msg| msg.value().is_some()))
+	}
+
+	/// Resolves the requested language as [`Self::try_resolve_language()`] does, then picks the
+	/// language to take the given message from as [`Self::message_language()`] does.
+	fn resolve_message_language(&self, key: &str, language: &str) -> Result<&str> {
+		self.try_resolve_language(language)
+			.map(|resolved| self.message_language(key, resolved))
+	}
+
+	/// Picks the language to take a message from once the requested one was resolved. Languages
+	/// with private-use subtags, such as `en-x-pirate`, usually only override a few messages, so
+	/// the language they are based on is used for the others, falling back as usual.
+	fn message_language<'a>(&'a self, key: &str, resolved: &'a str) -> &'a str {
+		let Some(base_end) = resolved.find("-x-") else {
+			return resolved;
+		};
+		if self.loaded_bundle(resolved).is_ok_and(|bundle| bundle.has_message(&self.internal_key(key))) {
+			return resolved;
+		}
+
+		match self.negotiate(&resolved[..base_end]) {
+			Some(base) => base,
+			None if self.options.no_default_fallback => resolved,
+			None => &self.default_language
+		}
+	}
+
+	/// Extracts an attribute of a message from the requested bundle, falling back like
+	/// [`Self::get_message()`] does.
+	pub fn get_attribute(&self, key: &str, attribute: &str, language: &str, args: Option<&FluentArgs>) -> Result<String> {
+		let resolved = self.resolve_message_language(key, language)?;
+		let bundle = self.loaded_bundle(resolved)?;
+		let pattern = bundle.get_message(&self.internal_key(key))
+			.and_then(|msg| msg.get_attribute(attribute))
+			.map(|attr| attr.value())
+			.ok_or_else(|| error::Error::MissingMessage { key: format!("{}.{}", key, attribute), language: language.to_string() })?;
+
+		self.format_pattern(bundle, pattern, args, &format_args!("{}.{}", key, attribute), resolved)
+	}
+
+	/// Extracts a term, whose identifier is to be given without the leading `-`, falling back
+	/// like [`Self::get_message()`] does.
+	pub fn get_term(&self, id: &str, language: &str, args: Option<&FluentArgs>) -> Result<String> {
+		let resolved = self.try_resolve_language(language)?;
+		let bundle = self.loaded_bundle(resolved)?;
+		let term = self.term_entries(resolved)
+			.find(|term| term.id.name == id)
+			.ok_or_else(|| error::Error::MissingMessage { key: format!("-{}", id), language: language.to_string() })?;
+
+		self.format_pattern(bundle, &term.value, args, &format_args!("-{}", id), resolved)
+	}
+
+	/// Like [`Self::get_message()`], but keeps the type of the result when the message's value
+	/// is nothing but a variable or a number literal: `{ $count }` gives back the argument passed
+	/// as `count` untouched, and `{ 42 }` gives a [`FluentValue::Number`]. Anything else is
+	/// formatted as usual and returned as a [`FluentValue::String`].
+	pub fn get_value(&self, key: &str, language: &str, args: Option<&FluentArgs>) -> Result<FluentValue<'static>> {
+		let resolved = self.resolve_message_language(key, language)?;
+		let bundle = self.loaded_bundle(resolved)?;
+		let pattern = self.message_value(bundle, key, &language)?;
+
+		if let [ast::PatternElement::Placeable { expression: ast::Expression::Inline(inline) }] = pattern.elements.as_slice() {
+			match inline {
+				ast::InlineExpression::VariableReference { id } => {
+					if let Some(value) = args.and_then(|args| args.get(id.name)) {
+						if self.options.strict_args {
+							Self::check_args(pattern, args)?;
+						}
+						return Ok(value.into_owned());
+					}
+				},
+				ast::InlineExpression::NumberLiteral { value } => return Ok(FluentValue::try_number(value).into_owned()),
+				_ => {}
+			}
+		}
+
+		self.format_pattern(bundle, pattern, args, &key, resolved).map(FluentValue::from)
+	}
+
+	/// Lists the names of the attributes defined on a message, taken from the requested bundle
+	/// with the same fallback as [`Self::get_message()`].
+	pub fn attributes(&self, key: &str, language: &str) -> Result<Vec<String>> {
+		let bundle = self.loaded_bundle(self.resolve_message_language(key, language)?)?;
+		let msg = bundle.get_message(&self.internal_key(key))
+			.ok_or_else(|| error::Error::MissingMessage { key: key.to_string(), language: language.to_string() })?;
+
+		Ok(msg.attributes().map(|attr| attr.id().to_string()).collect())
+	}
+
+	/// Starts building a request for the message with the given key. See [MessageRequest].
+	pub fn message<'a>(&'a self, key: &'a str) -> MessageRequest<'a> {
+		MessageRequest::new(self, key)
+	}
+
+	/// Finds the loaded language that best matches the requested one, trying progressively less
+	/// specific versions of it (`it-IT` → `it`) and the default language last. Languages written
+	/// in the same script are preferred, so that `zh-Hant-HK` resolves to `zh-Hant` or `zh-TW`
+	/// rather than to `zh-Hans`.
+	///
+	/// This always falls back on the default language, even if
+	/// [`LocaliserBuilder::fallback_to_default()`] was disabled.
+	pub fn resolve_language(&self, language: &str) -> &str {
+		self.negotiate(language)
+			.unwrap_or(&self.default_language)
+	}
+
+	/// Like [`Self::resolve_language()`], but takes an already parsed [LanguageIdentifier]. If
+	/// the language is available, it is found without allocating.
+	pub fn resolve_language_id(&self, language: &LanguageIdentifier) -> &str {
+		self.find_language_id(language)
+			.unwrap_or_else(|| self.resolve_language(&language.to_string()))
+	}
+
+	/// Finds the available language that best matches the requested one, if any, trying the
+	/// fallback chain declared in the manifest before anything else.
+	fn negotiate(&self, language: &str) -> Option<&str> {
+		if self.options.manifest.is_some() && !self.available_languages.contains_key(language) {
+			if let Some(code) = self.manifest_fallback(language) {
+				return Some(code);
+			}
+		}
+
+		fallback::negotiate(language, &self.available_languages)
+	}
+
+	/// Finds the code of an available language that is exactly the given one, without allocating.
+	fn find_language_id(&self, language: &LanguageIdentifier) -> Option<&str> {
+		self.available_languages.iter()
+			.find(|(code, id)| *id == language && !fallback::is_private_use(code))
+			.map(|(code, _)| code.as_str())
+	}
+
+	/// Like [`Self::resolve_language()`], but fails with [`error::Error::MissingLanguageError`]
+	/// instead of falling back on the default language if
+	/// [`LocaliserBuilder::fallback_to_default()`] was disabled and nothing matches.
+	pub(crate) fn try_resolve_language(&self, language: &str) -> Result<&str> {
+		match self.negotiate(language) {
+			Some(resolved) => Ok(resolved),
+			None if self.options.no_default_fallback => Err(error::Error::MissingLanguageError(language.to_string())),
+			None => Ok(&self.default_language)
+		}
+	}
+
+	/// Gets the bundle for the requested language, falling back as described in
+	/// [`Self::try_resolve_language()`].
+	pub(crate) fn resolve_bundle(&self, language: &str) -> Result<&TypedFluentBundle> {
+		self.loaded_bundle(self.try_resolve_language(language)?)
+	}
+
+	/// Gets the bundle of a language that was already resolved, without negotiating again.
+	fn loaded_bundle(&self, language: &str) -> Result<&TypedFluentBundle> {
+		if let Some(lazy) = self.lazy.get(language) {
+			return Ok(&self.load_lazy(lazy)?.bundle);
+		}
+
+		self.bundles.get(language)
+			.map(Arc::as_ref)
+			.ok_or_else(|| error::Error::GenericError("Failed to get default bundle! This is not supposed to happen!".to_string()))
+	}
+
+	/// Gets the value of a message from the given bundle, failing with
+	/// [`error::Error::MessageHasNoValue`] if it only has attributes. The requested language is
+	/// only used for errors.
+	fn message_value<'b>(&self, bundle: &'b TypedFluentBundle, key: &str, language: &dyn std::fmt::Display) -> Result<&'b ast::Pattern<&'b str>> {
+		let msg = bundle.get_message(&self.internal_key(key))
+			.ok_or_else(|| error::Error::MissingMessage { key: key.to_string(), language: language.to_string() })?;
+		msg.value()
+			.ok_or_else(|| error::Error::MessageHasNoValue { key: key.to_string(), language: language.to_string() })
+	}
+
+	/// Formats a pattern taken from the given bundle, failing if Fluent reports any error. The key
+	/// and language the pattern belongs to are only used for errors.
+	fn format_pattern(&self, bundle: &TypedFluentBundle, pattern: &ast::Pattern<&str>, args: Option<&FluentArgs>, key: &dyn std::fmt::Display, language: &str) -> Result<String> {
+		if self.options.strict_args {
+			Self::check_args(pattern, args)?;
+		}
+
+		let mut err = Vec::new();
+		// formatters are also applied to the whole result, which only format_pattern() does
+		let res = match pattern.elements.as_slice() {
+			[ast::PatternElement::TextElement { .. }] => bundle.format_pattern(pattern, args, &mut err).into_owned(),
+			_ if self.options.has_formatter() => bundle.format_pattern(pattern, args, &mut err).into_owned(),
+			elements => {
+				let mut res = String::with_capacity(Self::estimate_len(elements));
+				bundle.write_pattern(&mut res, pattern, args, &mut err)
+					.map_err(|_| error::Error::GenericError("Failed to write message!".to_string()))?;
+				res
+			}
+		};
+		if err.is_empty() {
+			Ok(res)
+		} else {
+			Err(error::FormatError::wrap(err, key, language))
+		}
+	}
+
+	/// Guesses how long a pattern will be once formatted, so that it can be written without
+	/// growing the buffer more than once: the text is counted as it is, and each placeable as
+	/// a short value wrapped in isolation marks.
+	fn estimate_len(elements: &[ast::PatternElement<&str>]) -> usize {
+		elements.iter()
+			.map(|element| match element {
+				ast::PatternElement::TextElement { value } => value.len(),
+				ast::PatternElement::Placeable { .. } => 16
+			}).sum()
+	}
+
+	/// Checks that the given arguments match the variables used by a pattern.
+	fn check_args(pattern: &ast::Pattern<&str>, args: Option<&FluentArgs>) -> Result<()> {
+		let expected = validate::pattern_variables(pattern);
+		let passed = args.into_iter()
+			.flat_map(|args| args.iter())
+			.map(|(name, _)| name)
+			.collect::<BTreeSet<_>>();
+
+		if expected == passed {
+			Ok(())
+		} else {
+			Err(error::Error::ArgumentMismatch {
+				missing: expected.difference(&passed).map(|v| v.to_string()).collect(),
+				unused: passed.difference(&expected).map(|v| v.to_string()).collect()
+			})
+		}
+	}
+}
+
+impl Localiser {
+	/// Counts the messages of each available language, in alphabetical order, without loading
+	/// languages that are loaded lazily: those that were not used yet have [None].
+	fn loaded_message_counts(&self) -> BTreeMap<&str, Option<usize>> {
+		self.available_languages.keys()
+			.map(|lang| (lang.as_str(), self.loaded_resources(lang).map(|resources| {
+				resources.iter()
+					.flat_map(|res| res.resource.entries())
+					.filter_map(|entry| match entry {
+						ast::Entry::Message(msg) => Some(msg.id.name),
+						_ => None
+					})
+					.collect::<HashSet<_>>()
+					.len()
+			})))
+			.collect()
+	}
+}
+
+/// Shows the default language and the number of messages of each language, without loading
+/// languages that are loaded lazily: those that were not used yet are shown as [None].
+impl fmt::Debug for Localiser {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Localiser")
+			.field("default_language", &self.default_language)
+			.field("messages_per_language", &self.loaded_message_counts())
+			.finish_non_exhaustive()
+	}
+}
+
+/// Shows the default language, the available languages and the number of messages across all
+/// of them on a single line. Languages loaded lazily that were not used yet don't count towards
+/// the messages.
+///
+/// ```rust
+/// use fluent_fluently::{InMemory, Localiser};
+///
+/// let source = InMemory::new()
+///     .with_resource("en-US", "hello = Hello!\nbye = Bye!")
+///     .with_resource("it", "hello = Ciao!");
+/// let loc = Localiser::try_load_from(source, "en-US").unwrap();
+/// assert_eq!(loc.to_string(), "Localiser(default=en-US, languages=[en-US, it], messages=3)");
+/// assert!(format!("{:?}", loc).contains(r#"messages_per_language: {"en-US": Some(2), "it": Some(1)}"#));
+/// ```
+impl fmt::Display for Localiser {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let counts = self.loaded_message_counts();
+		let languages = counts.keys().copied().collect::<Vec<_>>();
+		let messages = counts.values().flatten().sum::<usize>();
+		write!(f, "Localiser(default={}, languages=[{}], messages={})", self.default_language, languages.join(", "), messages)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+	use crate::{error::Error, fluent_args, FluentValue, InMemory, Localiser, LocaliserBuilder};
+
+	fn localiser() -> Localiser {
+		let source = InMemory::new()
+			.with_resource("en-US", "hello = Hello!\nbye = Bye!\ngreeting = Hello, { $name }!")
+			.with_resource("it", "hello = Ciao!");
+		Localiser::try_load_from(source, "en-US").unwrap()
+	}
+
+	#[test]
+	fn loads_from_memory() {
+		let loc = localiser();
+		assert_eq!(loc.default_language(), "en-US");
+		assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hello!");
+		assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+		assert_eq!(loc.get_message("greeting", "en-US", Some(&fluent_args!{ name => "Ada" })).unwrap(), "Hello, \u{2068}Ada\u{2069}!");
+	}
+
+	#[test]
+	fn falls_back() {
+		let loc = localiser();
+		assert_eq!(loc.get_message("hello", "it-IT", None).unwrap(), "Ciao!");
+		assert_eq!(loc.get_message("hello", "fr", None).unwrap(), "Hello!");
+		assert!(matches!(loc.get_message("bye", "it", None), Err(Error::MissingMessage { .. })));
+	}
+
+	#[test]
+	fn fails_to_load() {
+		let source = InMemory::new().with_resource("it", "hello = Ciao!");
+		assert!(matches!(Localiser::try_load_from(source, "ja"), Err(Error::DefaultLanguageMissing(_))));
+		assert!(matches!(Localiser::try_load_from(InMemory::new(), "en-US"), Err(Error::NoLanguagesLoaded)));
+		let source = InMemory::new().with_resource("en-US", "hello = {");
+		assert!(matches!(Localiser::try_load_from(source, "en-US"), Err(Error::LoadErrors(_))));
+	}
+
+	#[test]
+	fn inserts_at_runtime() {
+		let mut loc = localiser();
+		loc.insert_from_reader("fr", "hello = Bonjour !".as_bytes()).unwrap();
+		assert_eq!(loc.get_message("hello", "fr", None).unwrap(), "Bonjour !");
+		loc.remove_language("fr").unwrap();
+		assert_eq!(loc.get_message("hello", "fr", None).unwrap(), "Hello!");
+	}
+
+	fn pirate() -> Localiser {
+		let source = InMemory::new()
+			.with_resource("en", "hello = Hello!\nbye = Bye, { $name }!\n    .title = Farewell\ncount = { $n }")
+			.with_resource("en-x-pirate", "hello = Ahoy!\n    .title = Greeting");
+		Localiser::try_load_from(source, "en").unwrap()
+	}
+
+	#[test]
+	fn private_use_falls_back_on_base_language() {
+		let loc = pirate();
+		let args = fluent_args!{ name => "Ada", n => 3 };
+		let cases: [(&str, &dyn Fn() -> String, &str); 11] = [
+			("get_message", &|| loc.get_message("hello", "en-x-pirate", None).unwrap(), "Ahoy!"),
+			("get_message from base", &|| loc.get_message("bye", "en-x-pirate", Some(&args)).unwrap(), "Bye, \u{2068}Ada\u{2069}!"),
+			("get_message of base", &|| loc.get_message("hello", "en", None).unwrap(), "Hello!"),
+			("get_message_by_id of base", &|| loc.get_message_by_id("hello", &"en".parse().unwrap(), None).unwrap(), "Hello!"),
+			("get_message_partial", &|| {
+				let (msg, errors) = loc.get_message_partial("bye", "en-x-pirate", None).unwrap();
+				format!("{} ({} errors)", msg, errors.len())
+			}, "Bye, \u{2068}{$name}\u{2069}! (1 errors)"),
+			("write_message", &|| {
+				let mut out = String::new();
+				loc.write_message(&mut out, "bye", "en-x-pirate", Some(&args)).unwrap();
+				out
+			}, "Bye, \u{2068}Ada\u{2069}!"),
+			("has_message", &|| format!("{} {}", loc.has_message("bye", "en-x-pirate"), loc.has_message("missing", "en-x-pirate")), "true false"),
+			("get_attribute", &|| loc.get_attribute("hello", "title", "en-x-pirate", None).unwrap(), "Greeting"),
+			("get_attribute from base", &|| loc.get_attribute("bye", "title", "en-x-pirate", None).unwrap(), "Farewell"),
+			("get_value", &|| format!("{:?}", loc.get_value("count", "en-x-pirate", Some(&args)).unwrap()), &format!("{:?}", FluentValue::from(3))),
+			("attributes", &|| loc.attributes("bye", "en-x-pirate").unwrap().join(", "), "title")
+		];
+
+		for (method, get, expected) in cases {
+			assert_eq!(get(), expected, "{}", method);
+		}
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn reloads_from_disk() {
+		let root = std::env::temp_dir().join("fluent-fluently-reload");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join("en-US")).unwrap();
+		std::fs::write(root.join("en-US").join("main.ftl"), "hello = Hello!").unwrap();
+
+		let mut loc = Localiser::try_load(&root, "en-US").unwrap();
+		let old = loc.clone();
+		std::fs::write(root.join("en-US").join("main.ftl"), "hello = Hi!").unwrap();
+		std::fs::write(root.join("en-US").join("extra.ftl"), "bye = Bye!").unwrap();
+		std::fs::write(root.join("it.ftl"), "hello = Ciao!").unwrap();
+		loc.reload().unwrap();
+		assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hi!");
+		assert_eq!(loc.get_message("bye", "en-US", None).unwrap(), "Bye!");
+		assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+		assert_eq!(old.get_message("hello", "en-US", None).unwrap(), "Hello!");
+		assert!(!old.has_message("bye", "en-US"));
+
+		std::fs::write(root.join("it.ftl"), "hello = Ciao!\nhello = Salve!").unwrap();
+		assert!(loc.reload().is_err());
+		assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+		assert_eq!(loc.get_message("bye", "en-US", None).unwrap(), "Bye!");
+		std::fs::remove_dir_all(root).unwrap();
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn fails_without_default_directory() {
+		let root = std::env::temp_dir().join("fluent-fluently-no-default");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join("it")).unwrap();
+		std::fs::write(root.join("it").join("main.ftl"), "hello = Ciao!").unwrap();
+		std::fs::write(root.join("fr.ftl"), "hello = Bonjour !").unwrap();
+
+		assert!(matches!(Localiser::try_load(&root, "en-US"), Err(Error::DefaultLanguageMissing(lang)) if lang == "en-US"));
+		assert!(matches!(Localiser::builder(&root, "en-US").lazy(true).build(), Err(Error::DefaultLanguageMissing(_))));
+		assert_eq!(Localiser::try_load(&root, "it").unwrap().get_message("hello", "fr", None).unwrap(), "Bonjour !");
+		std::fs::remove_dir_all(root).unwrap();
+	}
+
+	#[test]
+	fn strict_args() {
+		let loc = LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", "hello = Hello, { $name }!\nfiles = { $count ->\n    [one] One file in { $folder }\n   *[other] { $count } files\n}\nlogin = Log in\n    .title = Log in as { $user }"), "en-US")
+			.strict_args(true)
+			.configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+			.build().unwrap();
+
+		assert_eq!(loc.get_message("hello", "en-US", Some(&fluent_args!{ name => "Anna" })).unwrap(), "Hello, Anna!");
+		assert!(matches!(loc.get_message("hello", "en-US", Some(&fluent_args!{ user_name => "Anna" })),
+			Err(Error::ArgumentMismatch { missing, unused }) if missing == ["name"] && unused == ["user_name"]));
+		assert!(matches!(loc.get_message("hello", "en-US", None),
+			Err(Error::ArgumentMismatch { missing, unused }) if missing == ["name"] && unused.is_empty()));
+		assert!(matches!(loc.get_message("hello", "en-US", Some(&fluent_args!{ name => "Anna", extra => 1 })),
+			Err(Error::ArgumentMismatch { missing, unused }) if missing.is_empty() && unused == ["extra"]));
+
+		// variables of variants that are not selected are still expected
+		assert_eq!(loc.get_message("files", "en-US", Some(&fluent_args!{ count => 3, folder => "Inbox" })).unwrap(), "3 files");
+		assert!(matches!(loc.get_message("files", "en-US", Some(&fluent_args!{ count => 3 })),
+			Err(Error::ArgumentMismatch { missing, .. }) if missing == ["folder"]));
+
+		assert_eq!(loc.get_attribute("login", "title", "en-US", Some(&fluent_args!{ user => "Anna" })).unwrap(), "Log in as Anna");
+		assert!(matches!(loc.get_attribute("login", "title", "en-US", Some(&fluent_args!{ name => "Anna" })),
+			Err(Error::ArgumentMismatch { .. })));
+		assert!(matches!(loc.get_message_partial("hello", "en-US", None), Err(Error::ArgumentMismatch { .. })));
+		assert!(matches!(loc.write_message(&mut String::new(), "hello", "en-US", None), Err(Error::ArgumentMismatch { .. })));
+	}
+
+	#[test]
+	fn finds_comments() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "### Resource comment\n\n# Greets the user.\n# Shown at startup.\nhello = Hello!\n\n## Farewells\n\nbye = Bye!\n")
+			.with_resource("en-US", "# Standalone comment\n\n# Saves the file.\nsave = Save")
+			.with_resource("it", "hello = Ciao!"), "en-US").unwrap();
+		assert_eq!(loc.comment("hello", "en-US").as_deref(), Some("Greets the user.\nShown at startup."));
+		assert_eq!(loc.comment("bye", "en-US"), None);
+		assert_eq!(loc.comment("save", "en-US").as_deref(), Some("Saves the file."));
+		assert_eq!(loc.comment("hello", "it"), None);
+		assert_eq!(loc.comment("missing", "en-US"), None);
+		assert_eq!(loc.comment("hello", "fr"), None);
+	}
+
+	#[test]
+	fn indexes_messages() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "zoom = Zoom\nhello = Hello!\n-brand = Firefox")
+			.with_resource("en-US", "bye = Bye!\nabout = About"), "en-US").unwrap();
+		let index = loc.message_index("en-US").unwrap();
+		assert_eq!(index.into_iter().collect::<Vec<_>>(), [
+			("about".to_string(), PathBuf::from("en-US/1")),
+			("bye".to_string(), PathBuf::from("en-US/1")),
+			("hello".to_string(), PathBuf::from("en-US/0")),
+			("zoom".to_string(), PathBuf::from("en-US/0"))
+		]);
+		assert!(matches!(loc.message_index("fr"), Err(Error::MissingLanguageError(lang)) if lang == "fr"));
+	}
+
+	#[test]
+	fn gets_terms() {
+		let loc = LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", "-brand = { $case ->\n    [genitive] Firefox's\n   *[nominative] Firefox\n}\n-product = { -brand } Browser\nhello = Hello!")
+			.with_resource("it", "-brand = Firefox"), "en-US")
+			.configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+			.build().unwrap();
+
+		assert_eq!(loc.term_keys("en-US").unwrap(), ["brand", "product"]);
+		assert_eq!(loc.term_keys("it").unwrap(), ["brand"]);
+		assert!(matches!(loc.term_keys("fr"), Err(Error::MissingLanguageError(lang)) if lang == "fr"));
+
+		assert_eq!(loc.get_term("brand", "en-US", Some(&fluent_args!{ case => "genitive" })).unwrap(), "Firefox's");
+		assert_eq!(loc.get_term("brand", "en-US", Some(&fluent_args!{ case => "dative" })).unwrap(), "Firefox");
+		assert!(matches!(loc.get_term("brand", "en-US", None), Err(Error::FormatErrors(errors)) if errors[0].key == "-brand"));
+		assert_eq!(loc.get_term("product", "en-US", None).unwrap(), "Firefox Browser");
+		assert_eq!(loc.get_term("brand", "it-IT", None).unwrap(), "Firefox");
+		assert!(matches!(loc.get_term("missing", "en-US", None),
+			Err(Error::MissingMessage { key, language }) if key == "-missing" && language == "en-US"));
+		assert!(matches!(loc.get_term("product", "it", None), Err(Error::MissingMessage { key, .. }) if key == "-product"));
+	}
+
+	#[test]
+	fn sets_default_language() {
+		let mut loc = localiser();
+		assert!(matches!(loc.set_default_language("ja"), Err(Error::MissingLanguageError(lang)) if lang == "ja"));
+		assert_eq!(loc.default_language(), "en-US");
+		assert!(loc.set_default_language("not a language!").is_err());
+		assert_eq!(loc.default_language(), "en-US");
+
+		loc.set_default_language("IT").unwrap();
+		assert_eq!(loc.default_language(), "it");
+		assert_eq!(loc.get_message("hello", "fr", None).unwrap(), "Ciao!");
+		assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hello!");
+		assert!(matches!(loc.get_message("bye", "fr", None), Err(Error::MissingMessage { .. })));
+	}
+
+	#[test]
+	fn lists_sources() {
+		let loc = LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("_shared", "-brand = Fluently")
+			.with_resource("en-US", "hello = Hello!")
+			.with_resource("en-US", "bye = Bye!")
+			.with_resource("it", "hello = Ciao!"), "en-US")
+			.shared("_shared")
+			.lazy(true)
+			.build().unwrap();
+		assert_eq!(loc.sources("en-US"), ["-brand = Fluently", "hello = Hello!", "bye = Bye!"]);
+		assert_eq!(loc.sources("it"), ["-brand = Fluently", "hello = Ciao!"]);
+		assert!(loc.sources("fr").is_empty());
+	}
+
+	#[test]
+	fn gets_values() {
+		let loc = LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", "count = { $count }\nanswer = { 42 }\nhello = Hello, { $name }!\nlogin =\n    .title = Log in")
+			.with_resource("it", "answer = { 42.5 }"), "en-US")
+			.configure_bundle(|bundle, _| bundle.set_use_isolating(false))
+			.build().unwrap();
+
+		assert!(matches!(loc.get_value("count", "en-US", Some(&fluent_args!{ count => 3 })).unwrap(), FluentValue::Number(n) if n.value == 3.0));
+		assert_eq!(loc.get_value("count", "en-US", Some(&fluent_args!{ count => "three" })).unwrap(), FluentValue::from("three"));
+		assert!(matches!(loc.get_value("answer", "en-US", None).unwrap(), FluentValue::Number(n) if n.value == 42.0));
+		assert_eq!(loc.get_value("hello", "en-US", Some(&fluent_args!{ name => "Ada" })).unwrap(), FluentValue::from("Hello, Ada!"));
+		assert!(matches!(loc.get_value("answer", "it-IT", None).unwrap(), FluentValue::Number(n) if n.value == 42.5));
+		assert!(matches!(loc.get_value("hello", "it", None), Err(Error::MissingMessage { .. })));
+		assert!(matches!(loc.get_value("login", "en-US", None), Err(Error::MessageHasNoValue { .. })));
+		assert!(matches!(loc.get_value("missing", "en-US", None), Err(Error::MissingMessage { .. })));
+	}
+
+	#[test]
+	fn lists_attributes() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "login = Log in\n    .title = Log in\n    .aria-label = Log in now\nhello = Hello!\nsave =\n    .title = Save")
+			.with_resource("it", "login = Accedi\n    .title = Accedi"), "en-US").unwrap();
+		assert_eq!(loc.attributes("login", "en-US").unwrap(), ["title", "aria-label"]);
+		assert_eq!(loc.attributes("login", "it-IT").unwrap(), ["title"]);
+		assert_eq!(loc.attributes("save", "fr").unwrap(), ["title"]);
+		assert!(loc.attributes("hello", "en-US").unwrap().is_empty());
+		assert!(matches!(loc.attributes("missing", "en-US"), Err(Error::MissingMessage { key, .. }) if key == "missing"));
+	}
 }
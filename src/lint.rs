@@ -0,0 +1,195 @@
+use std::fmt;
+use crate::{duplicate::Duplicate, validate::{BrokenReference, PlaceableMismatch, SelectorIssue}, Localiser};
+
+/// How serious a [LintIssue] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+	/// Something that is likely fine, but worth a look.
+	Note,
+	/// Something that makes a translation incomplete, without making anything fail.
+	Warning,
+	/// Something that makes formatting fail or produce the wrong output.
+	Error
+}
+
+/// A problem found by [`Localiser::lint()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintIssue {
+	/// A message of the default language is missing from another one.
+	MissingKey {
+		/// The language the message is missing from.
+		language: String,
+		/// The identifier of the message.
+		key: String
+	},
+	/// A message is defined in a language but not in the default one.
+	ExtraKey {
+		/// The language defining the message.
+		language: String,
+		/// The identifier of the message.
+		key: String
+	},
+	/// A message has the same value as in the default language. See [`Localiser::untranslated()`].
+	Untranslated {
+		/// The language of the translation.
+		language: String,
+		/// The identifier of the message.
+		key: String
+	},
+	/// A translation uses different variables than the default language.
+	/// See [`Localiser::validate_placeables()`].
+	PlaceableMismatch(PlaceableMismatch),
+	/// A reference that does not resolve. See [`Localiser::validate_references()`].
+	BrokenReference(BrokenReference),
+	/// A select expression listing the same variant key more than once.
+	/// See [`Localiser::validate_selectors()`].
+	RepeatedVariant(SelectorIssue),
+	/// A message or term defined more than once, if they were tracked.
+	/// See [`Localiser::duplicates()`].
+	Duplicate(Duplicate)
+}
+
+impl LintIssue {
+	/// How serious this is.
+	pub fn severity(&self) -> Severity {
+		match self {
+			Self::PlaceableMismatch(_) | Self::BrokenReference(_) => Severity::Error,
+			Self::MissingKey { .. } | Self::RepeatedVariant(_) | Self::Duplicate(_) => Severity::Warning,
+			Self::ExtraKey { .. } | Self::Untranslated { .. } => Severity::Note
+		}
+	}
+
+	/// The language this was found in.
+	pub fn language(&self) -> &str {
+		match self {
+			Self::MissingKey { language, .. } | Self::ExtraKey { language, .. } | Self::Untranslated { language, .. } => language,
+			Self::PlaceableMismatch(mismatch) => &mismatch.language,
+			Self::BrokenReference(reference) => &reference.language,
+			Self::RepeatedVariant(issue) => &issue.language,
+			Self::Duplicate(duplicate) => &duplicate.language
+		}
+	}
+}
+
+impl fmt::Display for LintIssue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::MissingKey { language, key } => write!(f, "{}: {} is missing", language, key),
+			Self::ExtraKey { language, key } => write!(f, "{}: {} is not in the default language", language, key),
+			Self::Untranslated { language, key } => write!(f, "{}: {} is the same as in the default language", language, key),
+			Self::PlaceableMismatch(mismatch) => {
+				write!(f, "{}: {}", mismatch.language, mismatch.message)?;
+				if let Some(attribute) = &mismatch.attribute {
+					write!(f, ".{}", attribute)?;
+				}
+				write!(f, " uses different variables than the default language")?;
+				if !mismatch.missing.is_empty() {
+					write!(f, "; missing: ${}", mismatch.missing.join(", $"))?;
+				}
+				if !mismatch.extra.is_empty() {
+					write!(f, "; extra: ${}", mismatch.extra.join(", $"))?;
+				}
+				Ok(())
+			},
+			Self::BrokenReference(reference) => write!(f, "{}: {} references {}, which does not exist", reference.language, reference.message, reference.reference),
+			Self::RepeatedVariant(issue) => write!(f, "{}: {} has more than one [{}] variant", issue.language, issue.message_id, issue.key),
+			Self::Duplicate(duplicate) => write!(f, "{}: {} is defined in both {} and {}", duplicate.language, duplicate.key, duplicate.shadowed_file.display(), duplicate.winning_file.display())
+		}
+	}
+}
+
+/// Everything [`Localiser::lint()`] found, grouped by [Severity]. Within each group, issues are
+/// sorted by language, and come in a fixed order within each language, so that the output is
+/// the same on every run. Its [Display](fmt::Display) implementation lists them all, one per
+/// line, most serious first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintReport {
+	/// Issues that make formatting fail or produce the wrong output.
+	pub errors: Vec<LintIssue>,
+	/// Issues that make a translation incomplete.
+	pub warnings: Vec<LintIssue>,
+	/// Issues that are likely fine, but worth a look.
+	pub notes: Vec<LintIssue>
+}
+
+impl LintReport {
+	/// Whether nothing at all was found.
+	pub fn is_empty(&self) -> bool {
+		self.errors.is_empty() && self.warnings.is_empty() && self.notes.is_empty()
+	}
+
+	/// Whether there are no errors, ignoring warnings and notes. Meant as the exit condition of
+	/// a CI check.
+	pub fn is_ok(&self) -> bool {
+		self.errors.is_empty()
+	}
+}
+
+impl fmt::Display for LintReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let groups = [("error", &self.errors), ("warning", &self.warnings), ("note", &self.notes)];
+		let mut first = true;
+		for (label, issues) in groups {
+			for issue in issues {
+				if !first {
+					writeln!(f)?;
+				}
+				first = false;
+				write!(f, "{}: {}", label, issue)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Localiser {
+	/// Runs every check at once and collects what they found: messages missing from or extra
+	/// to translations as found by [`Self::check_coverage()`], translations identical to the
+	/// default language, mismatched variables, broken references, repeated variant keys and, if
+	/// they were tracked, duplicate definitions. Meant to be printed by a CI script or
+	/// command-line tool. Languages loaded lazily are loaded.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello, { $name }!\nbye = Bye!\nok = OK")
+	///     .with_resource("it", "hello = Ciao!\nok = OK\nextra = { missing }"), "en-US").unwrap();
+	/// let report = loc.lint();
+	/// assert!(!report.is_ok());
+	/// assert_eq!(report.to_string(), "\
+	/// error: it: hello uses different variables than the default language; missing: $name
+	/// error: it: extra references missing, which does not exist
+	/// warning: it: bye is missing
+	/// note: it: extra is not in the default language
+	/// note: it: ok is the same as in the default language");
+	/// ```
+	pub fn lint(&self) -> LintReport {
+		let mut issues = Vec::new();
+		let mut coverage = self.check_coverage().languages.into_iter().collect::<Vec<_>>();
+		coverage.sort_by(|(a, _), (b, _)| a.cmp(b));
+		for (language, coverage) in coverage {
+			issues.extend(coverage.missing.into_iter().map(|key| LintIssue::MissingKey { language: language.clone(), key }));
+			issues.extend(coverage.extra.into_iter().map(|key| LintIssue::ExtraKey { language: language.clone(), key }));
+			issues.extend(self.untranslated(&language).unwrap_or_default().into_iter()
+				.map(|key| LintIssue::Untranslated { language: language.clone(), key }));
+		}
+
+		issues.extend(self.validate_placeables().into_iter().map(LintIssue::PlaceableMismatch));
+		issues.extend(self.validate_references().into_iter().map(LintIssue::BrokenReference));
+		issues.extend(self.validate_selectors().into_iter().map(LintIssue::RepeatedVariant));
+		issues.extend(self.duplicates().iter().cloned().map(LintIssue::Duplicate));
+		// stable, so the order of each check is kept within a language
+		issues.sort_by(|a, b| a.language().cmp(b.language()));
+
+		let mut report = LintReport::default();
+		for issue in issues {
+			match issue.severity() {
+				Severity::Error => report.errors.push(issue),
+				Severity::Warning => report.warnings.push(issue),
+				Severity::Note => report.notes.push(issue)
+			}
+		}
+		report
+	}
+}
@@ -0,0 +1,51 @@
+/// Builds a [FluentArgs](crate::FluentArgs) from a list of `key => value` pairs. Keys may be
+/// either identifiers or string literals, the latter being useful for names containing dashes.
+/// Values may be anything implementing `Into<FluentValue>`, such as strings and numbers.
+/// It is also available under the shorter name `args!`.
+///
+/// ```rust
+/// let args = fluent_fluently::fluent_args!{ name => "Ada", "unread-count" => 3 };
+/// assert!(args.get("name").is_some());
+/// assert!(args.get("unread-count").is_some());
+///
+/// let args = fluent_fluently::args!{ "name" => String::from("Ada") };
+/// assert!(args.get("name").is_some());
+/// ```
+#[macro_export]
+macro_rules! fluent_args {
+	(@key $key:ident) => { stringify!($key) };
+	(@key $key:literal) => { $key };
+	($($key:tt => $value:expr),* $(,)?) => {{
+		#[allow(unused_mut)]
+		let mut args = $crate::FluentArgs::new();
+		$(args.set($crate::fluent_args!(@key $key), $value);)*
+		args
+	}};
+}
+
+/// Shorthand for [`Localiser::get_message()`](crate::Localiser::get_message), taking the
+/// arguments in the same form as [`fluent_args!`]. Evaluates to the [Result](crate::error::Result)
+/// returned by it, so `?` can be used on it.
+///
+/// ```rust
+/// # fn main() -> fluent_fluently::error::Result<()> {
+/// use fluent_fluently::{localise, InMemory, Localiser};
+///
+/// let loc = Localiser::try_load_from(InMemory::new()
+///     .with_resource("it", "title = Benvenuto!\ngreeting = Ciao, { $name }!"), "it")?;
+/// let title = localise!(loc, "title", "it")?;
+/// let greeting = localise!(loc, "greeting", "it", name => "Ada")?;
+/// assert_eq!(title, "Benvenuto!");
+/// assert_eq!(greeting, "Ciao, \u{2068}Ada\u{2069}!");
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! localise {
+	($localiser:expr, $key:expr, $language:expr $(,)?) => {
+		$localiser.get_message($key, $language, None)
+	};
+	($localiser:expr, $key:expr, $language:expr, $($args:tt)+) => {
+		$localiser.get_message($key, $language, Some(&$crate::fluent_args!{ $($args)+ }))
+	};
+}
@@ -0,0 +1,192 @@
+use std::result::Result as StdResult;
+use crate::{error::{Error, Result}, fallback, Localiser};
+
+/// The name of the manifest file, looked for at the root of a [FileSystem](crate::FileSystem).
+pub const MANIFEST_NAME: &str = "locales.toml";
+
+/// Declares which languages to load, and how, instead of relying on whatever is found. When a
+/// [ResourceSource](crate::ResourceSource) has a manifest, only the languages it lists and
+/// enables are loaded, and loading fails if any of them is not found; anything else is ignored.
+///
+/// Manifests are written in a subset of TOML: a table for each language, named after its code,
+/// holding any of `name` (a string), `fallback` (an array of language codes) and `enabled` (a
+/// boolean, `true` if omitted). Values must fit on a single line.
+///
+/// ```rust
+/// use fluent_fluently::manifest::Manifest;
+///
+/// let manifest = Manifest::parse(r#"
+/// [en-US]
+/// name = "English" # shown in language pickers
+///
+/// [pt-PT]
+/// name = "Português"
+/// fallback = ["pt-BR", "es"]
+/// enabled = false
+/// "#).unwrap();
+/// assert_eq!(manifest.languages()[0].code, "en-US");
+/// assert_eq!(manifest.language("pt-PT").unwrap().fallback, ["pt-BR", "es"]);
+/// assert!(!manifest.language("pt-PT").unwrap().enabled);
+/// assert!(Manifest::parse("[en-US]\ncolour = \"blue\"").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+	/// The languages declared, in the order they are declared in.
+	languages: Vec<ManifestLanguage>
+}
+
+/// A language declared in a [Manifest].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestLanguage {
+	/// The code of the language, normalised.
+	pub code: String,
+	/// The name the language should be shown with, if given.
+	pub name: Option<String>,
+	/// The languages to use when this one is requested but not loaded, in order, before falling
+	/// back on the default one.
+	pub fallback: Vec<String>,
+	/// Whether the language should be loaded.
+	pub enabled: bool
+}
+
+impl Manifest {
+	/// Parses the contents of a manifest, failing if anything in it is not understood, including
+	/// invalid language codes and unknown keys.
+	pub fn parse(source: &str) -> Result<Self> {
+		let mut languages: Vec<ManifestLanguage> = Vec::new();
+		for (i, line) in source.lines().enumerate() {
+			let invalid = |reason: &str| Error::GenericError(format!("Invalid manifest, line {}: {}!", i + 1, reason));
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			if let Some(header) = line.strip_prefix('[') {
+				let (code, rest) = header.split_once(']').ok_or_else(|| invalid("unterminated table name"))?;
+				Value::end(rest).ok_or_else(|| invalid("unexpected content after the table name"))?;
+				let code = fallback::parse_code(code.trim().trim_matches('"'))
+					.map_err(|_| invalid("invalid language code"))?
+					.0;
+				if languages.iter().any(|lang| lang.code == code) {
+					return Err(invalid("language declared twice"));
+				}
+				languages.push(ManifestLanguage { code, name: None, fallback: Vec::new(), enabled: true });
+				continue;
+			}
+
+			let language = languages.last_mut().ok_or_else(|| invalid("key outside of a language table"))?;
+			let (key, value) = line.split_once('=').ok_or_else(|| invalid("expected a key and a value"))?;
+			let value = Value::parse(value.trim()).ok_or_else(|| invalid("invalid value"))?;
+			match (key.trim(), value) {
+				("name", Value::String(name)) => language.name = Some(name),
+				("fallback", Value::Array(codes)) => language.fallback = codes.iter()
+					.map(|code| fallback::parse_code(code).map(|(code, _)| code))
+					.collect::<StdResult<_, _>>()
+					.map_err(|_| invalid("invalid language code"))?,
+				("enabled", Value::Bool(enabled)) => language.enabled = enabled,
+				("name" | "fallback" | "enabled", _) => return Err(invalid("wrong type of value")),
+				_ => return Err(invalid("unknown key"))
+			}
+		}
+
+		Ok(Self { languages })
+	}
+
+	/// Lists the languages declared, in the order they are declared in.
+	pub fn languages(&self) -> &[ManifestLanguage] {
+		&self.languages
+	}
+
+	/// Finds the declaration of the given language, if there is one.
+	pub fn language(&self, code: &str) -> Option<&ManifestLanguage> {
+		let code = fallback::parse_code(code).ok()?.0;
+		self.languages.iter().find(|lang| lang.code == code)
+	}
+}
+
+/// A value in a manifest.
+enum Value {
+	/// A basic string, between double quotes.
+	String(String),
+	/// An array of strings.
+	Array(Vec<String>),
+	/// A boolean.
+	Bool(bool)
+}
+
+impl Value {
+	/// Parses a whole value, followed by nothing but a comment.
+	fn parse(value: &str) -> Option<Self> {
+		if let Some(rest) = value.strip_prefix("true") {
+			return Self::end(rest).map(|_| Self::Bool(true));
+		}
+		if let Some(rest) = value.strip_prefix("false") {
+			return Self::end(rest).map(|_| Self::Bool(false));
+		}
+		if let Some(mut rest) = value.strip_prefix('[') {
+			let mut items = Vec::new();
+			loop {
+				rest = rest.trim_start();
+				if let Some(after) = rest.strip_prefix(']') {
+					return Self::end(after).map(|_| Self::Array(items));
+				}
+				let (item, after) = Self::string(rest)?;
+				items.push(item);
+				rest = after.trim_start();
+				if let Some(after) = rest.strip_prefix(',') {
+					rest = after;
+				} else if !rest.starts_with(']') {
+					return None;
+				}
+			}
+		}
+		let (string, rest) = Self::string(value)?;
+		Self::end(rest).map(|_| Self::String(string))
+	}
+
+	/// Parses a basic string at the start of the given text, returning it along with what
+	/// follows it.
+	fn string(value: &str) -> Option<(String, &str)> {
+		let mut chars = value.strip_prefix('"')?.char_indices();
+		let mut string = String::new();
+		while let Some((i, c)) = chars.next() {
+			match c {
+				'"' => return Some((string, &value[i + 2..])),
+				'\\' => string.push(match chars.next()?.1 {
+					'"' => '"',
+					'\\' => '\\',
+					'n' => '\n',
+					't' => '\t',
+					_ => return None
+				}),
+				c => string.push(c)
+			}
+		}
+		None
+	}
+
+	/// Checks that nothing but whitespace and a comment is left.
+	fn end(rest: &str) -> Option<()> {
+		let rest = rest.trim_start();
+		(rest.is_empty() || rest.starts_with('#')).then_some(())
+	}
+}
+
+impl Localiser {
+	/// Returns the manifest the languages were loaded according to, if the source had one.
+	pub fn manifest(&self) -> Option<&Manifest> {
+		self.options.manifest.as_ref()
+	}
+
+	/// Finds the first available language in the fallback chain the manifest declares for the
+	/// requested one, if it is declared and not loaded.
+	pub(crate) fn manifest_fallback(&self, language: &str) -> Option<&str> {
+		let declared = self.options.manifest.as_ref()?.language(language)?;
+		if self.available_languages.contains_key(&declared.code) {
+			return None;
+		}
+
+		declared.fallback.iter()
+			.find_map(|code| fallback::negotiate(code, &self.available_languages))
+	}
+}
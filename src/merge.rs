@@ -0,0 +1,255 @@
+use std::{collections::BTreeSet, sync::Arc};
+use fluent_syntax::ast;
+use crate::{error::{Error, Result}, namespace::NamespacedIds, LoadedResource, Localiser, ReloadEvent};
+
+/// What [`Localiser::merge()`] does when both localisers define the same message or term in
+/// the same language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+	/// Use the definition from the localiser being merged in.
+	#[default]
+	PreferIncoming,
+	/// Keep the definition that was already there.
+	PreferExisting,
+	/// Fail with [`Error::MergeConflicts`], leaving everything as it was.
+	Error
+}
+
+/// Which of the two localisers passed to [`Localiser::merge()`] a definition comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide {
+	/// The localiser being merged into.
+	Existing,
+	/// The localiser being merged in.
+	Incoming
+}
+
+/// A message or term that both localisers passed to [`Localiser::merge()`] define in the same
+/// language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+	/// The language both define it in.
+	pub language: String,
+	/// The identifier of the message or term; terms are prefixed with `-`, as they would be in FTL.
+	pub key: String,
+	/// The localiser whose definition is used.
+	pub kept: MergeSide
+}
+
+/// What [`Localiser::merge()`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+	/// The languages that were only available in the localiser being merged in, sorted
+	/// alphabetically.
+	pub added_languages: Vec<String>,
+	/// The messages and terms both localisers defined, and how each was resolved, sorted by
+	/// language and then by key.
+	pub conflicts: Vec<MergeConflict>
+}
+
+impl Localiser {
+	/// Folds the languages of another [Localiser] into this one, for instance to add the
+	/// translations of a plugin discovered after startup. Languages only found in `other` are
+	/// added with the resources shared by this one coming first, as if they had been loaded
+	/// here, while the bundles of languages found in both are rebuilt out of the resources of
+	/// both, with the settings of this one. Messages namespaced by `other` can be looked up by
+	/// the same keys afterwards.
+	///
+	/// Resources that both have, such as those added through
+	/// [`LocaliserBuilder::shared()`](crate::LocaliserBuilder::shared), are only kept once and
+	/// don't count as conflicts. Since bundles are rebuilt from their resources, this fails with
+	/// [`Error::UnknownResources`] if a language of either was built elsewhere and handed over
+	/// through [`Self::from_bundles()`], and nothing changes.
+	///
+	/// When both define the same message or term in the same language, the [MergePolicy]
+	/// decides which definition is used, regardless of the
+	/// [DuplicatePolicy](crate::DuplicatePolicy) either was loaded with. Each of these cases is
+	/// listed in the returned [MergeReport]. The default language of this one is kept.
+	/// Languages of either that are loaded lazily are loaded first: if any of them fails to,
+	/// the error is returned and nothing changes. Subscribers are sent a single [ReloadEvent]
+	/// listing the languages of `other`.
+	///
+	/// ```rust
+	/// use fluent_fluently::{merge::{MergePolicy, MergeSide}, InMemory, Localiser};
+	///
+	/// let mut loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!\nbye = Bye!"), "en-US").unwrap();
+	/// let plugin = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "bye = Goodbye!")
+	///     .with_resource("it", "bye = Arrivederci!"), "en-US").unwrap();
+	///
+	/// let report = loc.merge(plugin, MergePolicy::PreferIncoming).unwrap();
+	/// assert_eq!(report.added_languages, ["it"]);
+	/// assert_eq!(report.conflicts[0].key, "bye");
+	/// assert_eq!(report.conflicts[0].kept, MergeSide::Incoming);
+	/// assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hello!");
+	/// assert_eq!(loc.get_message("bye", "en-US", None).unwrap(), "Goodbye!");
+	/// assert_eq!(loc.get_message("bye", "it", None).unwrap(), "Arrivederci!");
+	/// ```
+	pub fn merge(&mut self, other: Localiser, policy: MergePolicy) -> Result<MergeReport> {
+		let kept = match policy {
+			MergePolicy::PreferExisting => MergeSide::Existing,
+			_ => MergeSide::Incoming
+		};
+		let mut report = MergeReport::default();
+		let mut merged = Vec::new();
+		for (language, id) in other.available_languages.iter() {
+			// new languages start from the shared resources, as if they had been loaded here
+			let added = !self.available_languages.contains_key(language);
+			let existing = match added {
+				true => self.shared.to_vec(),
+				false => self.owned_resources(language)?
+			};
+			let incoming = other.owned_resources(language)?.into_iter()
+				.filter(|res| !existing.iter().any(|known| Self::same_resource(known, res)))
+				.collect::<Vec<_>>();
+
+			// whatever comes last overrides what came before, and languages override what is shared
+			let resources = if added {
+				report.added_languages.push(language.clone());
+				existing.into_iter().chain(incoming).collect::<Vec<_>>()
+			} else {
+				report.conflicts.extend(Self::defined_keys(&existing)
+					.intersection(&Self::defined_keys(&incoming))
+					.map(|key| MergeConflict { language: language.clone(), key: self.public_conflict_key(&other, key), kept }));
+				match kept {
+					MergeSide::Existing => incoming.into_iter().chain(existing).collect::<Vec<_>>(),
+					MergeSide::Incoming => existing.into_iter().chain(incoming).collect::<Vec<_>>()
+				}
+			};
+			let mut bundle = self.options.new_bundle(id);
+			for res in &resources {
+				bundle.add_resource_overriding(res.resource.clone());
+			}
+
+			merged.push((language.clone(), id.clone(), bundle, resources));
+		}
+
+		report.added_languages.sort();
+		report.conflicts.sort_by(|a, b| (&a.language, &a.key).cmp(&(&b.language, &b.key)));
+		if policy == MergePolicy::Error && !report.conflicts.is_empty() {
+			for conflict in &mut report.conflicts {
+				conflict.kept = MergeSide::Existing;
+			}
+			return Err(Error::MergeConflicts(report.conflicts));
+		}
+
+		// the identifiers namespaced by either are known by their keys in both
+		if !Arc::ptr_eq(&self.options.namespaced_ids, &other.options.namespaced_ids) {
+			let ids = NamespacedIds::merged(&self.options.namespaced_ids, &other.options.namespaced_ids);
+			Arc::make_mut(&mut self.options).namespaced_ids = Arc::new(ids);
+		}
+
+		let mut languages = merged.into_iter()
+			.map(|(language, id, bundle, resources)| self.store_language(language, id, bundle, resources))
+			.collect::<Vec<_>>();
+		languages.sort();
+		self.subscribers.notify(ReloadEvent { languages });
+		Ok(report)
+	}
+
+	/// Gets a copy of the resources of the given language, loading it if it is loaded lazily.
+	/// Languages that are not available have none, while those whose resources are not known
+	/// fail with [`Error::UnknownResources`].
+	fn owned_resources(&self, language: &str) -> Result<Vec<LoadedResource>> {
+		if let Some(lazy) = self.lazy.get(language) {
+			return Ok(self.load_lazy(lazy)?.resources.clone());
+		}
+
+		match self.resources.get(language) {
+			Some(resources) => Ok(resources.clone()),
+			None if self.available_languages.contains_key(language) => Err(Error::UnknownResources(language.to_string())),
+			None => Ok(Vec::new())
+		}
+	}
+
+	/// Checks whether two resources are the same, either because they are shared or because
+	/// they were read from the same place and have the same contents.
+	fn same_resource(a: &LoadedResource, b: &LoadedResource) -> bool {
+		Arc::ptr_eq(&a.resource, &b.resource) || (a.path == b.path && a.resource.source() == b.resource.source())
+	}
+
+	/// Turns the identifier of a message or term both localisers define into the key users know
+	/// it by, as either of them reports it.
+	fn public_conflict_key(&self, other: &Localiser, id: &str) -> String {
+		match self.public_key(id) {
+			key if key != id => key,
+			_ => other.public_key(id)
+		}
+	}
+
+	/// Collects the identifiers of the messages and terms defined by the given resources, terms
+	/// being prefixed with `-`.
+	fn defined_keys(resources: &[LoadedResource]) -> BTreeSet<String> {
+		resources.iter()
+			.flat_map(|res| res.resource.entries())
+			.filter_map(|entry| match entry {
+				ast::Entry::Message(msg) => Some(msg.id.name.to_string()),
+				ast::Entry::Term(term) => Some(format!("-{}", term.id.name)),
+				_ => None
+			}).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::HashMap, sync::Arc};
+	use fluent::FluentResource;
+	use crate::{error::Error, InMemory, Localiser, LocaliserBuilder, MergePolicy, TypedFluentBundle};
+
+	fn from_bundle(source: &str) -> Localiser {
+		let mut bundle = TypedFluentBundle::new_concurrent(vec!["en-US".parse().unwrap()]);
+		bundle.add_resource(Arc::new(FluentResource::try_new(source.to_string()).unwrap())).unwrap();
+		Localiser::from_bundles(HashMap::from([("en-US".to_string(), bundle)]), "en-US").unwrap()
+	}
+
+	fn loaded(source: &str) -> Localiser {
+		Localiser::try_load_from(InMemory::new().with_resource("en-US", source), "en-US").unwrap()
+	}
+
+	#[test]
+	fn fails_without_resources() {
+		let mut loc = loaded("hello = Hello!");
+		assert!(matches!(loc.merge(from_bundle("bye = Bye!"), MergePolicy::PreferIncoming), Err(Error::UnknownResources(lang)) if lang == "en-US"));
+		assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hello!");
+
+		let mut loc = from_bundle("hello = Hello!");
+		assert!(matches!(loc.merge(loaded("bye = Bye!"), MergePolicy::PreferIncoming), Err(Error::UnknownResources(_))));
+		assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hello!");
+	}
+
+	#[test]
+	fn shared_resources_are_not_conflicts() {
+		let load = |language: &str, source: &str| LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("_shared", "-brand = Fluently")
+			.with_resource(language, source), language)
+			.shared("_shared")
+			.build()
+			.unwrap();
+		let mut loc = load("en-US", "hello = Hello from { -brand }!");
+		let report = loc.merge(load("en-US", "bye = Bye from { -brand }!"), MergePolicy::Error).unwrap();
+		assert!(report.conflicts.is_empty());
+		assert_eq!(loc.get_message("bye", "en-US", None).unwrap(), "Bye from Fluently!");
+		assert_eq!(loc.message_keys("en-US").unwrap(), ["hello", "bye"]);
+	}
+
+	#[test]
+	fn added_languages_get_shared_resources() {
+		let mut loc = LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("_shared", "-brand = Fluently\n-app = Fluently App")
+			.with_resource("en-US", "hello = Hello from { -brand }!"), "en-US")
+			.shared("_shared")
+			.build()
+			.unwrap();
+		let plugin = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", "bye = Bye!")
+			.with_resource("it", "hello = Ciao da { -brand }!\n-app = App di Fluently\nopen = Apri { -app }"), "en-US").unwrap();
+
+		let report = loc.merge(plugin, MergePolicy::Error).unwrap();
+		assert_eq!(report.added_languages, ["it"]);
+		assert!(report.conflicts.is_empty());
+		let unisolated = |text: String| text.replace(['\u{2068}', '\u{2069}'], "");
+		assert_eq!(unisolated(loc.get_message("hello", "it", None).unwrap()), "Ciao da Fluently!");
+		assert_eq!(unisolated(loc.get_message("open", "it", None).unwrap()), "Apri App di Fluently");
+	}
+}
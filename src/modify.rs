@@ -0,0 +1,263 @@
+use std::{io::Read, path::{Path, PathBuf}, sync::Arc};
+use fluent::FluentResource;
+use unic_langid::LanguageIdentifier;
+use crate::{builder::Options, error::{Error, Result}, stats::LanguageStats, LoadedResource, Localiser, ReloadEvent, TypedFluentBundle};
+
+/// The identifier given to resources that were not read from a [ResourceSource](crate::ResourceSource).
+const READER_PATH: &str = "<reader>";
+
+impl Localiser {
+	/// Reads FTL source text from any [Read] implementor, such as a network response or a
+	/// decompressor, and parses it into a [FluentResource].
+	///
+	/// ```rust
+	/// use fluent_fluently::Localiser;
+	///
+	/// let res = Localiser::resource_from_reader(std::io::Cursor::new(b"hello = Hello!")).unwrap();
+	/// assert_eq!(res.source(), "hello = Hello!");
+	/// ```
+	pub fn resource_from_reader<R: Read>(mut reader: R) -> Result<FluentResource> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes)?;
+		Self::bytes_to_resource(Path::new(READER_PATH), bytes, &LanguageIdentifier::default(), &Options::default())
+	}
+
+	/// Reads a resource from any [Read] implementor and makes it the only resource of the given
+	/// language, which is added if it was not available yet and replaced otherwise. See
+	/// [`Self::insert_from_readers()`].
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let mut loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello!"), "en-US").unwrap();
+	/// loc.insert_from_reader("it", std::io::Cursor::new("hello = Ciao!".as_bytes())).unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+	/// ```
+	pub fn insert_from_reader<R: Read>(&mut self, language: &str, reader: R) -> Result<()> {
+		self.insert_from_readers(language, std::iter::once(reader))
+	}
+
+	/// Reads a resource from each of the given [Read] implementors, in order, and makes them the
+	/// only resources of the given language, which is added if it was not available yet and
+	/// replaced otherwise. Resources are decoded in the [Encoding](crate::Encoding) the others
+	/// were read in, and the bundle is created with the same settings. Everything is read and
+	/// built before anything changes: if reading fails with [`Error::IoError`], decoding with
+	/// [`Error::InvalidEncoding`] or parsing with [`Error::ParseError`], the language is left as
+	/// it was. Subscribers are sent a [ReloadEvent].
+	///
+	/// ```rust
+	/// use std::io::{Cursor, Read};
+	/// use fluent_fluently::{error::Error, InMemory, Localiser};
+	///
+	/// let mut loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello!"), "en-US").unwrap();
+	/// let readers = [Cursor::new("hello = Ciao!".as_bytes()), Cursor::new("bye = Ciao ciao!".as_bytes())];
+	/// loc.insert_from_readers("it", readers).unwrap();
+	/// assert_eq!(loc.get_message("bye", "it", None).unwrap(), "Ciao ciao!");
+	///
+	/// let err = loc.insert_from_readers("it", [Cursor::new(b"hello = Ciao\xff".as_slice())]).unwrap_err();
+	/// assert!(matches!(err, Error::InvalidEncoding { .. }));
+	///
+	/// struct Broken;
+	/// impl Read for Broken {
+	///     fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+	///         Err(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "stream closed"))
+	///     }
+	/// }
+	/// assert!(matches!(loc.insert_from_reader("it", Broken).unwrap_err(), Error::IoError(_)));
+	/// assert_eq!(loc.get_message("bye", "it", None).unwrap(), "Ciao ciao!");
+	/// ```
+	pub fn insert_from_readers<R: Read>(&mut self, language: &str, readers: impl IntoIterator<Item = R>) -> Result<()> {
+		let (code, id) = crate::fallback::parse_code(language)?;
+		let resources = readers.into_iter()
+			.map(|mut reader| {
+				let mut bytes = Vec::new();
+				reader.read_to_end(&mut bytes)?;
+				Ok(LoadedResource {
+					path: PathBuf::from(READER_PATH),
+					resource: Arc::new(Self::bytes_to_resource(Path::new(READER_PATH), bytes, &id, &self.options)?)
+				})
+			})
+			.collect::<Result<Vec<_>>>()?;
+		self.set_language(code, id, resources)
+	}
+
+	/// Makes a language unavailable, as if it was never loaded, so that lookups in it fall back
+	/// as usual. Fails with [`Error::DefaultLanguageRemoval`] for the default language, and with
+	/// [`Error::MissingLanguageError`] if the language is not available. Subscribers are sent a
+	/// [ReloadEvent].
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let mut loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!"), "en-US").unwrap();
+	/// loc.remove_language("it").unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Hello!");
+	/// assert!(loc.remove_language("en-US").is_err());
+	/// ```
+	pub fn remove_language(&mut self, language: &str) -> Result<()> {
+		let language = crate::fallback::parse_code(language)?.0;
+		if language == self.default_language() {
+			return Err(Error::DefaultLanguageRemoval(language));
+		}
+		if !self.available_languages.contains_key(&language) {
+			return Err(Error::MissingLanguageError(language));
+		}
+
+		Arc::make_mut(&mut self.available_languages).remove(&language);
+		if self.bundles.contains_key(&language) {
+			Arc::make_mut(&mut self.bundles).remove(&language);
+			Arc::make_mut(&mut self.resources).remove(&language);
+			Arc::make_mut(&mut self.language_stats).remove(&language);
+		}
+		if self.lazy.contains_key(&language) {
+			Arc::make_mut(&mut self.lazy).remove(&language);
+		}
+		self.forget_reports(&language);
+		self.clear_message_cache();
+
+		self.subscribers.notify(ReloadEvent { languages: vec![language] });
+		Ok(())
+	}
+
+	/// Parses the given FTL sources and makes them the only resources of the given language,
+	/// which is added if it was not available yet. Every source is parsed and the bundle is
+	/// built before anything changes: if any of that fails, the error is returned and the
+	/// language is left as it was. Subscribers are sent a [ReloadEvent].
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let mut loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!"), "en-US").unwrap();
+	/// loc.replace_language("it", vec!["hello = Salve!".to_string()]).unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Salve!");
+	/// assert!(loc.replace_language("it", vec!["hello = {".to_string()]).is_err());
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Salve!");
+	/// ```
+	pub fn replace_language(&mut self, language: &str, sources: Vec<String>) -> Result<()> {
+		self.insert_from_readers(language, sources.into_iter().map(|source| std::io::Cursor::new(source.into_bytes())))
+	}
+
+	/// Reads a single language again from the source it was loaded from, leaving every other
+	/// language untouched, and swaps in the new bundle. If anything fails to load, the error is
+	/// returned and the language is left as it was. If the language is no longer in the source,
+	/// it is removed as through [`Self::remove_language()`], which fails for the default one.
+	/// A language that is not available yet is added if the source has it. Subscribers are sent
+	/// a [ReloadEvent].
+	///
+	/// ```rust
+	/// # #[cfg(feature = "fs")] {
+	/// use fluent_fluently::{FileSystem, Localiser};
+	///
+	/// let root = std::env::temp_dir().join("fluent-fluently-reload-language");
+	/// std::fs::create_dir_all(root.join("it")).unwrap();
+	/// std::fs::write(root.join("en-US.ftl"), "hello = Hello!").unwrap();
+	/// std::fs::write(root.join("it").join("main.ftl"), "hello = Ciao!").unwrap();
+	/// let mut loc = Localiser::try_load_from(FileSystem::new(&root), "en-US").unwrap();
+	///
+	/// std::fs::write(root.join("it").join("main.ftl"), "hello = Salve!").unwrap();
+	/// loc.reload_language("it").unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Salve!");
+	///
+	/// std::fs::write(root.join("it").join("main.ftl"), "hello = {").unwrap();
+	/// assert!(loc.reload_language("it").is_err());
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Salve!");
+	///
+	/// std::fs::remove_dir_all(root.join("it")).unwrap();
+	/// loc.reload_language("it").unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Hello!");
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// # }
+	/// ```
+	pub fn reload_language(&mut self, language: &str) -> Result<()> {
+		let (code, id) = crate::fallback::parse_code(language)?;
+		let manifest = self.options.manifest.as_ref();
+		let entry = self.source.languages()?.into_iter()
+			.filter(|entry| !self.options.shared.contains(&entry.name))
+			.filter(|entry| manifest.is_none_or(|manifest| manifest.language(&entry.name).is_some_and(|lang| lang.enabled)))
+			.find(|entry| crate::fallback::parse_code(&entry.name).is_ok_and(|(found, _)| found == code));
+
+		match entry {
+			Some(entry) => {
+				let resources = Self::read_resources(self.source.as_ref(), &id, &entry.resources, &self.options)?;
+				self.set_language(code, id, resources)
+			},
+			None => self.remove_language(language)
+		}
+	}
+
+	/// Builds a bundle for a language out of the shared resources followed by the given ones,
+	/// replacing whatever was loaded for it before, and notifies subscribers. The duplicates
+	/// found replace those recorded for the language before. Nothing changes if building fails.
+	pub(crate) fn set_language(&mut self, language: String, id: LanguageIdentifier, resources: Vec<LoadedResource>) -> Result<()> {
+		let resources = [&self.shared, resources.as_slice()].concat();
+		let (bundle, duplicates) = self.options.build_bundle(&id, &resources)?;
+		let language = self.store_language(language, id, bundle, resources);
+		if !duplicates.is_empty() {
+			self.duplicates = self.duplicates.iter().cloned().chain(duplicates).collect();
+		}
+		self.subscribers.notify(ReloadEvent { languages: vec![language] });
+		Ok(())
+	}
+
+	/// Forgets the duplicates and load failures recorded for a language, which no longer
+	/// describe what is loaded for it.
+	fn forget_reports(&mut self, language: &str) {
+		let is_language = |code: &str| crate::fallback::parse_code(code).is_ok_and(|(code, _)| code == language);
+		if self.duplicates.iter().any(|duplicate| is_language(&duplicate.language)) {
+			self.duplicates = self.duplicates.iter().filter(|duplicate| !is_language(&duplicate.language)).cloned().collect();
+		}
+		if self.load_failures.iter().any(|failure| is_language(&failure.language)) {
+			self.load_failures = self.load_failures.iter().filter(|failure| !is_language(&failure.language)).cloned().collect();
+		}
+	}
+
+	/// Replaces whatever was loaded for a language with an already built bundle, without
+	/// notifying subscribers. Returns the code the language is stored under.
+	pub(crate) fn store_language(&mut self, language: String, id: LanguageIdentifier, bundle: TypedFluentBundle, resources: Vec<LoadedResource>) -> String {
+		Arc::make_mut(&mut self.bundles).insert(language.clone(), Arc::new(bundle));
+		Arc::make_mut(&mut self.language_stats).insert(language.clone(), LanguageStats::of(&resources));
+		Arc::make_mut(&mut self.resources).insert(language.clone(), resources);
+		Arc::make_mut(&mut self.available_languages).insert(language.clone(), id);
+		self.forget_reports(&language);
+		self.clear_message_cache();
+		if self.lazy.contains_key(&language) {
+			Arc::make_mut(&mut self.lazy).remove(&language);
+		}
+
+		language
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{DuplicatePolicy, InMemory, LocaliserBuilder};
+
+	#[test]
+	fn replacing_updates_reports() {
+		let mut loc = LocaliserBuilder::with_source(InMemory::new()
+			.with_resource("en-US", "hello = Hello!")
+			.with_resource("it", "hello = Ciao!\nhello = Salve!")
+			.with_resource("fr", "hello = {"), "en-US")
+			.on_duplicate(DuplicatePolicy::OverrideAndReport)
+			.lenient_translations(true)
+			.build().unwrap();
+		assert_eq!(loc.duplicates().len(), 1);
+		assert_eq!(loc.load_failures().len(), 1);
+
+		loc.replace_language("it", vec!["hello = Ciao!\nbye = Ciao!\nbye = Arrivederci!".to_string()]).unwrap();
+		assert_eq!(loc.duplicates().len(), 1);
+		assert_eq!(loc.duplicates()[0].language, "it");
+		assert_eq!(loc.duplicates()[0].key, "bye");
+		assert_eq!(loc.get_message("bye", "it", None).unwrap(), "Arrivederci!");
+
+		loc.replace_language("it", vec!["hello = Ciao!".to_string()]).unwrap();
+		assert!(loc.duplicates().is_empty());
+
+		loc.replace_language("fr", vec!["hello = Bonjour !".to_string()]).unwrap();
+		assert!(loc.load_failures().is_empty());
+	}
+}
@@ -0,0 +1,233 @@
+use std::{borrow::Cow, collections::HashMap, sync::{atomic::{AtomicBool, Ordering}, RwLock}};
+use fluent::FluentResource;
+use fluent_syntax::ast;
+use crate::{builder::Options, error::{Error, Result}, validate::{pattern_references, Reference}, Localiser};
+
+/// What separates the parts of a namespaced key in the identifiers actually given to Fluent,
+/// which may not contain anything else that could tell them apart.
+const MARKER: &str = "__";
+
+/// The identifiers actually given to Fluent for the messages that were namespaced while loading,
+/// tied to the keys users know them by. Only these are reported differently from how the
+/// bundles know them, so that identifiers that merely look namespaced, such as `foo__bar` in a
+/// language made of a single file, are left alone.
+#[derive(Debug, Default)]
+pub(crate) struct NamespacedIds {
+	/// The identifiers and keys, both ways.
+	maps: RwLock<NamespacedMaps>,
+	/// Whether anything was ever remembered, so that localisers without namespaces never have
+	/// to take the lock.
+	used: AtomicBool
+}
+
+/// The contents of [NamespacedIds].
+#[derive(Debug, Default, Clone)]
+struct NamespacedMaps {
+	/// The keys users know each identifier by.
+	keys: HashMap<String, String>,
+	/// The identifiers given to Fluent for each key.
+	ids: HashMap<String, String>
+}
+
+impl NamespacedIds {
+	/// Remembers the key a namespaced identifier is known by.
+	fn insert(&self, id: String, key: String) {
+		// a map insertion can't leave things half-done, so a poisoned lock is still fine to use
+		let mut maps = self.maps.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+		maps.ids.insert(key.clone(), id.clone());
+		maps.keys.insert(id, key);
+		self.used.store(true, Ordering::Release);
+	}
+
+	/// Gets the key a namespaced identifier is known by, if it is one.
+	fn get(&self, id: &str) -> Option<String> {
+		if !self.used.load(Ordering::Acquire) {
+			return None;
+		}
+		self.maps.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.keys
+			.get(id)
+			.cloned()
+	}
+
+	/// Gets the identifier given to Fluent for a key, if it was namespaced.
+	fn id(&self, key: &str) -> Option<String> {
+		if !self.used.load(Ordering::Acquire) {
+			return None;
+		}
+		self.maps.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.ids
+			.get(key)
+			.cloned()
+	}
+
+	/// Creates a new instance holding what both of the given ones do, the second taking
+	/// precedence.
+	pub(crate) fn merged(a: &Self, b: &Self) -> Self {
+		let merged = Self::default();
+		for ids in [a, b] {
+			let maps = ids.maps.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+			for (id, key) in maps.keys {
+				merged.insert(id, key);
+			}
+		}
+		merged
+	}
+}
+
+/// Rewrites a resource so that the identifiers of its messages, and the references to them it
+/// contains, are prefixed with the given namespace, whose parts are separated by `/`. Terms are
+/// left untouched, so that they can still be shared across namespaces. The keys of the messages
+/// it defines or refers to are remembered in the options, see [NamespacedIds].
+pub(crate) fn namespace_resource(resource: FluentResource, namespace: &str, options: &Options) -> Result<FluentResource> {
+	let is_identifier = |part: &str| part.starts_with(|c: char| c.is_ascii_alphabetic())
+		&& part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+	if !namespace.split('/').all(is_identifier) {
+		return Err(Error::GenericError(format!("{} cannot be used as a namespace!", namespace)));
+	}
+
+	let source = resource.source();
+	let offset = |name: &str| name.as_ptr() as usize - source.as_ptr() as usize;
+	let mut offsets = Vec::new();
+	let mut ids = Vec::new();
+	for entry in resource.entries() {
+		let (id, value, attributes) = match entry {
+			ast::Entry::Message(msg) => (Some(msg.id.name), msg.value.as_ref(), &msg.attributes),
+			ast::Entry::Term(term) => (None, Some(&term.value), &term.attributes),
+			_ => continue
+		};
+		offsets.extend(id.map(offset));
+		ids.extend(id);
+		for pattern in value.into_iter().chain(attributes.iter().map(|attr| &attr.value)) {
+			for reference in pattern_references(pattern) {
+				if let Reference::Message { id, .. } = reference {
+					offsets.push(offset(id));
+					ids.push(id);
+				}
+			}
+		}
+	}
+	offsets.sort_unstable();
+
+	let prefix = format!("{}{}", namespace.replace('/', MARKER), MARKER);
+	let mut namespaced = String::with_capacity(source.len() + offsets.len() * prefix.len());
+	let mut last = 0;
+	for offset in offsets {
+		namespaced.push_str(&source[last..offset]);
+		namespaced.push_str(&prefix);
+		last = offset;
+	}
+	namespaced.push_str(&source[last..]);
+
+	if let Some(separator) = &options.namespace_separator {
+		let public = namespace.replace('/', separator);
+		for id in ids {
+			options.namespaced_ids.insert(format!("{}{}", prefix, id), format!("{}{}{}", public, separator, id));
+		}
+	}
+
+	FluentResource::try_new(namespaced)
+		.map_err(|_| Error::GenericError(format!("Failed to apply namespace {}!", namespace)))
+}
+
+impl Options {
+	/// Like [`Localiser::public_key()`], for when there is no [Localiser] yet.
+	pub(crate) fn public_key(&self, id: &str) -> String {
+		self.namespaced_ids.get(id)
+			.unwrap_or_else(|| id.to_string())
+	}
+}
+
+impl Localiser {
+	/// Turns a key as given by users into the identifier Fluent knows the message by, which
+	/// only differ if namespaces are enabled, either here or in a [Localiser] merged into this.
+	pub(crate) fn internal_key<'k>(&self, key: &'k str) -> Cow<'k, str> {
+		if let Some(id) = self.options.namespaced_ids.id(key) {
+			return Cow::Owned(id);
+		}
+
+		match &self.options.namespace_separator {
+			Some(separator) if key.contains(separator.as_str()) => Cow::Owned(key.replace(separator.as_str(), MARKER)),
+			_ => Cow::Borrowed(key)
+		}
+	}
+
+	/// Turns the identifier Fluent knows a message by into the key users look it up with; the
+	/// opposite of [`Self::internal_key()`]. Only identifiers that were actually namespaced while
+	/// loading are changed.
+	pub(crate) fn public_key(&self, id: &str) -> String {
+		self.options.public_key(id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+	use crate::{error::Result, source::LanguageEntry, InMemory, Localiser, LocaliserBuilder, MergePolicy, ResourceSource};
+
+	/// Puts the first resource of each language in the `errors` namespace, and leaves the
+	/// others without one.
+	struct Namespaced(InMemory);
+
+	impl ResourceSource for Namespaced {
+		fn languages(&self) -> Result<Vec<LanguageEntry>> {
+			self.0.languages()
+		}
+
+		fn read(&self, resource: &Path) -> Result<Vec<u8>> {
+			self.0.read(resource)
+		}
+
+		fn namespace(&self, resource: &Path) -> Option<String> {
+			resource.ends_with("0").then(|| "errors".to_string())
+		}
+	}
+
+	fn load(source: InMemory) -> Localiser {
+		LocaliserBuilder::with_source(Namespaced(source), "en-US").namespaced("/").build().unwrap()
+	}
+
+	#[test]
+	fn only_namespaced_ids_are_rewritten() {
+		let loc = load(InMemory::new()
+			.with_resource("en-US", "title = Error")
+			.with_resource("en-US", "foo__bar = Literal"));
+		assert_eq!(loc.message_keys("en-US").unwrap(), ["errors/title", "foo__bar"]);
+		assert_eq!(loc.get_message("errors/title", "en-US", None).unwrap(), "Error");
+		assert_eq!(loc.get_message("foo__bar", "en-US", None).unwrap(), "Literal");
+	}
+
+	#[test]
+	fn reports_use_namespaced_keys() {
+		let loc = load(InMemory::new()
+			.with_resource("en-US", "title = Error { $code }\nbroken = { missing }")
+			.with_resource("it", "title = Errore"));
+		let broken = loc.validate_references();
+		assert_eq!(broken[0].message, "errors/broken");
+		assert_eq!(broken[0].reference, "errors/missing");
+		let mismatches = loc.validate_placeables();
+		assert_eq!(mismatches[0].message, "errors/title");
+
+		let mut loc = load(InMemory::new().with_resource("en-US", "title = Error"));
+		let report = loc.merge(load(InMemory::new().with_resource("en-US", "title = Failure")), MergePolicy::PreferIncoming).unwrap();
+		assert_eq!(report.conflicts[0].key, "errors/title");
+	}
+
+	#[test]
+	fn merged_namespaces_are_found() {
+		let mut loc = Localiser::try_load_from(InMemory::new().with_resource("en-US", "hello = Hello!"), "en-US").unwrap();
+		let before = loc.clone();
+		let plugin = load(InMemory::new()
+			.with_resource("en-US", "title = Error")
+			.with_resource("it", "title = Errore"));
+
+		let report = loc.merge(plugin, MergePolicy::Error).unwrap();
+		assert_eq!(report.added_languages, ["it"]);
+		assert_eq!(loc.message_keys("en-US").unwrap(), ["hello", "errors/title"]);
+		assert_eq!(loc.get_message("errors/title", "en-US", None).unwrap(), "Error");
+		assert_eq!(loc.get_message("errors/title", "it", None).unwrap(), "Errore");
+		assert!(before.get_message("errors/title", "en-US", None).is_err());
+	}
+}
@@ -0,0 +1,197 @@
+use std::sync::OnceLock;
+use fluent::{FluentArgs, FluentResource, FluentValue};
+use fluent_syntax::ast;
+use unic_langid::LanguageIdentifier;
+use crate::Localiser;
+
+pub use fluent::types::{FluentNumber, FluentNumberOptions};
+
+/// Gets the pattern used to format standalone values through a bundle: a single placeable, so
+/// that Fluent does not add isolation marks around it.
+fn value_pattern() -> &'static ast::Pattern<&'static str> {
+	static RESOURCE: OnceLock<FluentResource> = OnceLock::new();
+	let resource = RESOURCE.get_or_init(|| {
+		FluentResource::try_new("value = { $value }".to_string())
+			.expect("the value pattern is valid FTL")
+	});
+
+	match resource.get_entry(0) {
+		Some(ast::Entry::Message(ast::Message { value: Some(pattern), .. })) => pattern,
+		_ => unreachable!("the value resource contains a single message with a value")
+	}
+}
+
+/// The symbols numbers are written with in a language, according to CLDR.
+struct NumberSymbols {
+	/// What separates the integer part from the fraction.
+	decimal: char,
+	/// What separates groups of three digits in the integer part.
+	group: char,
+	/// How many digits the integer part must have beyond the first group to be grouped at all:
+	/// with 2, `1234` is left alone but `12345` is not.
+	min_grouping: usize
+}
+
+impl NumberSymbols {
+	/// Finds the symbols of a language, if it is one of those known.
+	fn of(language: &LanguageIdentifier) -> Option<Self> {
+		let (decimal, group, min_grouping) = match (language.language.as_str(), language.region.as_ref().map(|region| region.as_str())) {
+			("de" | "it", Some("CH" | "LI")) => ('.', '\u{2019}', 1),
+			("es", Some("MX" | "US" | "419")) => ('.', ',', 1),
+			("pt", Some("PT")) => (',', '\u{a0}', 2),
+			("en" | "ja" | "zh" | "ko" | "he" | "th" | "ms" | "ga" | "fil", _) => ('.', ',', 1),
+			("es", _) => (',', '.', 2),
+			("de" | "it" | "pt" | "nl" | "da" | "id" | "tr" | "el" | "ro" | "hr" | "sl" | "sr" | "vi" | "ca", _) => (',', '.', 1),
+			("fr", _) => (',', '\u{202f}', 1),
+			("pl" | "bg" | "et", _) => (',', '\u{a0}', 2),
+			("ru" | "uk" | "cs" | "sk" | "fi" | "sv" | "nb" | "no" | "hu" | "lt" | "lv", _) => (',', '\u{a0}', 1),
+			_ => return None
+		};
+		Some(Self { decimal, group, min_grouping })
+	}
+
+	/// Writes a number with these symbols, applying the digit options Fluent leaves out.
+	/// Numbers that are not finite are written as Rust would.
+	fn format(&self, value: f64, options: &FluentNumberOptions) -> String {
+		if !value.is_finite() {
+			return value.to_string();
+		}
+
+		let mut digits = match options.maximum_fraction_digits {
+			Some(max) => format!("{:.*}", max, value),
+			None => value.to_string()
+		};
+		if digits.contains('.') {
+			let min = options.minimum_fraction_digits.unwrap_or(0);
+			let fraction = digits.len() - digits.find('.').unwrap_or_default() - 1;
+			let trailing = digits.len() - digits.trim_end_matches('0').len();
+			digits.truncate(digits.len() - trailing.min(fraction.saturating_sub(min)));
+			digits = digits.trim_end_matches('.').to_string();
+		}
+
+		let (sign, digits) = match digits.strip_prefix('-') {
+			Some(digits) => ("-", digits),
+			None => ("", digits.as_str())
+		};
+		let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+		let fraction = format!("{:0<width$}", fraction, width = options.minimum_fraction_digits.unwrap_or(0));
+		let integer = format!("{:0>width$}", integer, width = options.minimum_integer_digits.unwrap_or(1));
+
+		let mut formatted = sign.to_string();
+		let grouped = options.use_grouping && integer.len() >= 3 + self.min_grouping;
+		for (i, digit) in integer.chars().enumerate() {
+			if grouped && i > 0 && (integer.len() - i) % 3 == 0 {
+				formatted.push(self.group);
+			}
+			formatted.push(digit);
+		}
+		if !fraction.is_empty() {
+			formatted.push(self.decimal);
+			formatted.push_str(&fraction);
+		}
+		formatted
+	}
+}
+
+impl Localiser {
+	/// Formats a number for the given language, falling back like [`Self::get_message()`] does.
+	/// See [`Self::format_number_with()`].
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en", "hello = Hello!")
+	///     .with_resource("de", "hello = Hallo!"), "en").unwrap();
+	/// assert_eq!(loc.format_number(1234.5, "en"), "1,234.5");
+	/// assert_eq!(loc.format_number(1234.5, "de"), "1.234,5");
+	/// assert_eq!(loc.format_number(-1234567.0, "de-AT"), "-1.234.567");
+	/// ```
+	pub fn format_number(&self, value: f64, language: &str) -> String {
+		self.format_number_with(value, &FluentNumberOptions::default(), language)
+	}
+
+	/// Like [`Self::format_number()`], but with the given options, such as the minimum and maximum
+	/// number of fraction digits.
+	///
+	/// The decimal and grouping separators are those CLDR gives for the language, for the most
+	/// common languages; others are written as Fluent would, with `.` and no grouping. A
+	/// formatter set through [`LocaliserBuilder::formatter()`](crate::LocaliserBuilder::formatter)
+	/// takes precedence over all of this. Only the options about digits and grouping are
+	/// applied: styles such as currencies are not.
+	///
+	/// Fluent itself does not apply locale-specific separators when formatting the arguments of
+	/// messages: pass the result of this as a string to get them. To pass a number with options
+	/// as an argument, so that it can still be used in selectors, wrap it in a [FluentNumber]:
+	///
+	/// ```rust
+	/// use fluent_fluently::{number::{FluentNumber, FluentNumberOptions}, FluentArgs};
+	///
+	/// let options = FluentNumberOptions { minimum_fraction_digits: Some(2), ..Default::default() };
+	/// let mut args = FluentArgs::new();
+	/// args.set("price", FluentNumber::new(4.5, options));
+	/// ```
+	pub fn format_number_with(&self, value: f64, options: &FluentNumberOptions, language: &str) -> String {
+		let number = FluentNumber::new(value, options.clone());
+		let Ok(resolved) = self.try_resolve_language(language) else {
+			return number.as_string().into_owned();
+		};
+		let Ok(bundle) = self.loaded_bundle(resolved) else {
+			return number.as_string().into_owned();
+		};
+
+		let plain = number.as_string();
+		let mut args = FluentArgs::new();
+		args.set("value", FluentValue::Number(number));
+		let formatted = bundle.format_pattern(value_pattern(), Some(&args), &mut Vec::new()).into_owned();
+		if formatted != plain {
+			// a custom formatter took care of it
+			return formatted;
+		}
+
+		match self.available_languages.get(resolved).and_then(NumberSymbols::of) {
+			Some(symbols) => symbols.format(value, options),
+			None => formatted
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{number::FluentNumberOptions, InMemory, Localiser};
+
+	fn localiser() -> Localiser {
+		let mut source = InMemory::new();
+		for language in ["en", "de", "fr", "es", "de-CH", "xx"] {
+			source = source.with_resource(language, "hello = Hello!");
+		}
+		Localiser::try_load_from(source, "en").unwrap()
+	}
+
+	#[test]
+	fn separators() {
+		let loc = localiser();
+		assert_eq!(loc.format_number(1234.5, "en"), "1,234.5");
+		assert_eq!(loc.format_number(1234.5, "de"), "1.234,5");
+		assert_eq!(loc.format_number(1234567.25, "fr"), "1\u{202f}234\u{202f}567,25");
+		assert_eq!(loc.format_number(1234.5, "de-CH"), "1\u{2019}234.5");
+		assert_eq!(loc.format_number(1234.0, "es"), "1234");
+		assert_eq!(loc.format_number(12345.0, "es"), "12.345");
+		assert_eq!(loc.format_number(123.0, "en"), "123");
+		assert_eq!(loc.format_number(-0.5, "de"), "-0,5");
+		assert_eq!(loc.format_number(1234.5, "xx"), "1234.5");
+	}
+
+	#[test]
+	fn options() {
+		let loc = localiser();
+		let options = |min, max| FluentNumberOptions { minimum_fraction_digits: min, maximum_fraction_digits: max, ..Default::default() };
+		assert_eq!(loc.format_number_with(1234.5, &options(Some(2), None), "de"), "1.234,50");
+		assert_eq!(loc.format_number_with(1.23456, &options(None, Some(2)), "en"), "1.23");
+		assert_eq!(loc.format_number_with(1.5, &options(None, Some(3)), "en"), "1.5");
+		assert_eq!(loc.format_number_with(1.0, &options(Some(1), Some(3)), "en"), "1.0");
+		assert_eq!(loc.format_number_with(2.999, &options(None, Some(0)), "en"), "3");
+		let ungrouped = FluentNumberOptions { use_grouping: false, ..Default::default() };
+		assert_eq!(loc.format_number_with(1234.5, &ungrouped, "en"), "1234.5");
+	}
+}
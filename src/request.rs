@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+use fluent::{FluentArgs, FluentValue};
+use crate::{error::Result, Localiser};
+
+/// A chainable request for a message, as an alternative to [`Localiser::get_message()`] for when
+/// there are several options to set. You can obtain a new instance by calling
+/// [`Localiser::message()`].
+///
+/// ```rust
+/// # let loc = fluent_fluently::Localiser::try_load_from(fluent_fluently::InMemory::new()
+/// #     .with_resource("it", "cart-items = { $count } articoli"), "it").unwrap();
+/// let msg = loc.message("cart-items").lang("it").arg("count", 3).get_or_key();
+/// assert_eq!(msg, "\u{2068}3\u{2069} articoli");
+/// ```
+pub struct MessageRequest<'a> {
+	/// The [Localiser] to take the message from.
+	localiser: &'a Localiser,
+	/// The key of the message.
+	key: &'a str,
+	/// The requested language; the default one is used if [None].
+	language: Option<&'a str>,
+	/// The attribute to get instead of the message's value, if any.
+	attribute: Option<&'a str>,
+	/// The arguments to format the message with.
+	args: Option<FluentArgs<'a>>,
+	/// Whether [`Self::get()`] should return the key instead of failing.
+	or_key: bool
+}
+
+impl<'a> MessageRequest<'a> {
+	/// Creates a new request for the given key, with no options set.
+	pub(crate) fn new(localiser: &'a Localiser, key: &'a str) -> Self {
+		Self {
+			localiser,
+			key,
+			language: None,
+			attribute: None,
+			args: None,
+			or_key: false
+		}
+	}
+
+	/// Sets the language to get the message in. If it's not available, or if this is never
+	/// called, the default language is used.
+	pub fn lang(mut self, language: &'a str) -> Self {
+		self.language = Some(language);
+		self
+	}
+
+	/// Adds an argument to format the message with.
+	pub fn arg(mut self, name: impl Into<Cow<'a, str>>, value: impl Into<FluentValue<'a>>) -> Self {
+		self.args.get_or_insert_with(FluentArgs::new).set(name, value);
+		self
+	}
+
+	/// Adds all the given arguments to the ones the message is formatted with.
+	pub fn args(mut self, args: FluentArgs<'a>) -> Self {
+		match &mut self.args {
+			Some(current) => for (name, value) in args {
+				current.set(name, value);
+			},
+			None => self.args = Some(args)
+		}
+		self
+	}
+
+	/// Requests the given attribute of the message instead of its value.
+	pub fn attribute(mut self, attribute: &'a str) -> Self {
+		self.attribute = Some(attribute);
+		self
+	}
+
+	/// Makes [`Self::get()`] return the key itself instead of failing.
+	pub fn or_key(mut self) -> Self {
+		self.or_key = true;
+		self
+	}
+
+	/// Gets the requested message.
+	pub fn get(&self) -> Result<String> {
+		let language = self.language.unwrap_or(self.localiser.default_language());
+		let args = self.args.as_ref();
+		let res = match self.attribute {
+			Some(attribute) => self.localiser.get_attribute(self.key, attribute, language, args),
+			None => self.localiser.get_message(self.key, language, args)
+		};
+
+		match res {
+			Err(_) if self.or_key => Ok(self.key.to_string()),
+			res => res
+		}
+	}
+
+	/// Gets the requested message, returning the key itself if anything goes wrong.
+	pub fn get_or_key(&self) -> String {
+		self.get().unwrap_or_else(|_| self.key.to_string())
+	}
+}
@@ -0,0 +1,78 @@
+use fluent::FluentArgs;
+use crate::{error::{Error, Result}, Localiser};
+
+/// A lightweight view over a [Localiser] bound to a single language.
+/// You can obtain a new instance by calling [`Localiser::scoped()`].
+pub struct ScopedLocaliser<'a> {
+	/// The [Localiser] this was created from.
+	localiser: &'a Localiser,
+	/// The language messages are looked up in.
+	language: String
+}
+
+impl<'a> ScopedLocaliser<'a> {
+	/// Creates a new instance, resolving the language against the loaded ones. If nothing
+	/// matches and falling back on the default language was disabled, the requested language is
+	/// kept as it is, so that lookups fail.
+	pub(crate) fn new(localiser: &'a Localiser, language: &str) -> Self {
+		let language = localiser.try_resolve_language(language).unwrap_or(language).to_string();
+		Self { localiser, language }
+	}
+
+	/// The language this was resolved to.
+	pub fn language(&self) -> &str {
+		&self.language
+	}
+
+	/// Extracts a message in the bound language, or from the default one if absent, unless
+	/// that was disabled through
+	/// [`LocaliserBuilder::fallback_to_default()`](crate::LocaliserBuilder::fallback_to_default).
+	/// If the default language lacks it too, the error refers to the bound one.
+	/// See [`Localiser::get_message()`].
+	pub fn get(&self, key: &str, args: Option<&FluentArgs>) -> Result<String> {
+		let res = self.localiser.get_message(key, &self.language, args);
+		let default = self.localiser.default_language();
+		if !matches!(res, Err(Error::MissingMessage { .. })) || self.localiser.options.no_default_fallback || self.language == default {
+			return res;
+		}
+
+		match self.localiser.get_message(key, default, args) {
+			Err(Error::MissingMessage { .. }) => res,
+			fallback => fallback
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{error::Error, InMemory, LocaliserBuilder};
+
+	fn source() -> InMemory {
+		InMemory::new()
+			.with_resource("en-US", "hello = Hello!\nbye = Bye!")
+			.with_resource("it", "hello = Ciao!")
+	}
+
+	#[test]
+	fn resolves_language() {
+		let loc = LocaliserBuilder::with_source(source(), "en-US").build().unwrap();
+		assert_eq!(loc.scoped("it-IT").language(), "it");
+		assert_eq!(loc.scoped("IT").language(), "it");
+		assert_eq!(loc.scoped("fr").language(), "en-US");
+		assert_eq!(loc.scoped("it-IT").get("hello", None).unwrap(), "Ciao!");
+		assert_eq!(loc.scoped("fr").get("hello", None).unwrap(), "Hello!");
+	}
+
+	#[test]
+	fn falls_back_on_missing_keys() {
+		let loc = LocaliserBuilder::with_source(source(), "en-US").build().unwrap();
+		let scoped = loc.scoped("it");
+		assert_eq!(scoped.get("bye", None).unwrap(), "Bye!");
+		assert!(matches!(scoped.get("missing", None), Err(Error::MissingMessage { key, language }) if key == "missing" && language == "it"));
+
+		let loc = LocaliserBuilder::with_source(source(), "en-US").fallback_to_default(false).build().unwrap();
+		assert_eq!(loc.scoped("fr").language(), "fr");
+		assert!(loc.scoped("fr").get("hello", None).is_err());
+		assert!(matches!(loc.scoped("it").get("bye", None), Err(Error::MissingMessage { .. })));
+	}
+}
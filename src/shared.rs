@@ -0,0 +1,231 @@
+use std::sync::{mpsc, Arc, RwLock};
+use crate::{error::Result, events::Subscribers, FluentArgs, Localiser, ReloadEvent};
+
+/// A handle to a [Localiser] that can be replaced while in use, meant to be handed to multiple
+/// threads or used as state by web frameworks, and to reload translations without restarting.
+/// Cloning it only increments a reference count, and all clones see the same [Localiser].
+/// You can obtain a new instance by calling [`Localiser::into_shared()`].
+///
+/// Every call works on a snapshot of the current [Localiser], obtained through [`Self::current()`]:
+/// the internal lock is only held for as long as it takes to copy an [Arc], never while
+/// formatting. When the [Localiser] is replaced through [`Self::reload()`], calls that already
+/// took their snapshot finish on the old one, and the following ones use the new one.
+/// Subscribers are sent a [ReloadEvent] every time the [Localiser] is replaced.
+#[derive(Clone)]
+pub struct SharedLocaliser {
+	/// The current [Localiser].
+	inner: Arc<RwLock<Arc<Localiser>>>,
+	/// The channels to notify whenever the [Localiser] is replaced, shared by all clones.
+	subscribers: Arc<Subscribers>
+}
+
+impl SharedLocaliser {
+	/// Wraps the given [Localiser]. Those who subscribed to it through
+	/// [`Localiser::subscribe()`] keep receiving events, now about this.
+	pub fn new(mut localiser: Localiser) -> Self {
+		let subscribers = Arc::new(std::mem::take(&mut localiser.subscribers));
+		Self { inner: Arc::new(RwLock::new(Arc::new(localiser))), subscribers }
+	}
+
+	/// Returns a channel that receives a [ReloadEvent] every time the [Localiser] is replaced,
+	/// through any clone of this. See [`Localiser::subscribe()`].
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser, ReloadEvent};
+	///
+	/// let load = |source: InMemory| Localiser::try_load_from(source, "en-US").unwrap();
+	/// let shared = load(InMemory::new().with_resource("en-US", "hello = Hello!")).into_shared();
+	/// let events = shared.clone().subscribe();
+	///
+	/// shared.reload(load(InMemory::new()
+	///     .with_resource("en-US", "hello = Hi!")
+	///     .with_resource("it", "hello = Ciao!")));
+	/// assert_eq!(events.try_recv().unwrap().languages, ["en-US", "it"]);
+	/// ```
+	pub fn subscribe(&self) -> mpsc::Receiver<ReloadEvent> {
+		self.subscribers.subscribe()
+	}
+
+	/// Returns a snapshot of the current [Localiser], to make several calls on the same one or to
+	/// use methods that are not available on this directly. It is not affected by later reloads.
+	pub fn current(&self) -> Arc<Localiser> {
+		// the lock is never held across anything that may panic, but be safe anyway
+		self.inner.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.clone()
+	}
+
+	/// Replaces the [Localiser] for this and all of its clones. Subscribers are sent a
+	/// [ReloadEvent] listing every language that was available before or after.
+	pub fn reload(&self, localiser: Localiser) {
+		let mut languages = localiser.available_languages.keys().cloned().collect::<Vec<_>>();
+		let old = std::mem::replace(&mut *self.inner.write().unwrap_or_else(|poisoned| poisoned.into_inner()), Arc::new(localiser));
+		languages.extend(old.available_languages.keys().cloned());
+		languages.sort();
+		languages.dedup();
+		self.subscribers.notify(ReloadEvent { languages });
+	}
+
+	/// Changes the [Localiser] for this and all of its clones through the given function, which
+	/// works on a copy of the current one, for instance to call [`Localiser::remove_language()`].
+	/// The copy only replaces the current one if the function succeeds. The internal lock is held
+	/// while the function runs, so that no other update is lost, which means that taking a new
+	/// snapshot waits for it: parse anything expensive beforehand. Subscribers are sent a single
+	/// [ReloadEvent] listing the languages the function changed, if any.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let shared = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("it", "hello = Ciao!"), "en-US").unwrap().into_shared();
+	/// shared.update(|loc| loc.remove_language("it")).unwrap();
+	/// assert_eq!(shared.get_message("hello", "it", None).unwrap(), "Hello!");
+	/// ```
+	pub fn update(&self, update: impl FnOnce(&mut Localiser) -> Result<()>) -> Result<()> {
+		let mut current = self.inner.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+		let mut localiser = Localiser::clone(&current);
+		let events = localiser.subscribe();
+		update(&mut localiser)?;
+		*current = Arc::new(localiser);
+		drop(current);
+
+		let mut languages = events.try_iter()
+			.flat_map(|event| event.languages)
+			.collect::<Vec<_>>();
+		if !languages.is_empty() {
+			languages.sort();
+			languages.dedup();
+			self.subscribers.notify(ReloadEvent { languages });
+		}
+		Ok(())
+	}
+
+	/// Loads a new [Localiser] as [`Localiser::try_load()`] does, and replaces the current one with
+	/// it, notifying subscribers as [`Self::reload()`] does. If loading fails, the current one is
+	/// kept and the error is returned.
+	#[cfg(feature = "fs")]
+	pub fn try_reload_from(&self, path: impl AsRef<std::path::Path>, default_language: &str) -> Result<()> {
+		self.reload(Localiser::try_load(path, default_language)?);
+		Ok(())
+	}
+
+	/// Calls [`Localiser::get_message()`] on the current [Localiser].
+	pub fn get_message(&self, key: &str, language: &str, args: Option<&FluentArgs>) -> Result<String> {
+		self.current().get_message(key, language, args)
+	}
+
+	/// Calls [`Localiser::has_message()`] on the current [Localiser].
+	pub fn has_message(&self, key: &str, language: &str) -> bool {
+		self.current().has_message(key, language)
+	}
+}
+
+impl From<Localiser> for SharedLocaliser {
+	fn from(localiser: Localiser) -> Self {
+		Self::new(localiser)
+	}
+}
+
+impl Localiser {
+	/// Turns this into a [SharedLocaliser], for sharing across threads and reloading.
+	pub fn into_shared(self) -> SharedLocaliser {
+		SharedLocaliser::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+	use crate::{InMemory, Localiser};
+	use super::SharedLocaliser;
+
+	fn version(text: &str, extra: bool) -> Localiser {
+		let mut source = format!("hello = {}", text);
+		if extra {
+			source.push_str("\nextra = Extra");
+		}
+		Localiser::try_load_from(InMemory::new().with_resource("en-US", source), "en-US").unwrap()
+	}
+
+	#[test]
+	fn is_send_and_sync() {
+		fn assert_send_sync<T: Clone + Send + Sync + 'static>() {}
+		assert_send_sync::<SharedLocaliser>();
+	}
+
+	#[test]
+	fn reloads_under_concurrent_readers() {
+		let (first, second) = (version("First", false), version("Second", true));
+		let shared = first.clone().into_shared();
+		let done = Arc::new(AtomicBool::new(false));
+
+		let readers = (0..4).map(|_| {
+			let shared = shared.clone();
+			let done = done.clone();
+			std::thread::spawn(move || {
+				let mut reads = 0;
+				while !done.load(Ordering::Relaxed) || reads == 0 {
+					let hello = shared.get_message("hello", "en-US", None).unwrap();
+					assert!(hello == "First" || hello == "Second");
+
+					// a snapshot is never torn, even if a reload happens meanwhile
+					let snapshot = shared.current();
+					let hello = snapshot.get_message("hello", "en-US", None).unwrap();
+					assert_eq!(snapshot.has_message("extra", "en-US"), hello == "Second");
+					reads += 1;
+				}
+				reads
+			})
+		}).collect::<Vec<_>>();
+
+		for i in 0..500 {
+			shared.reload(if i % 2 == 0 { second.clone() } else { first.clone() });
+		}
+		done.store(true, Ordering::Relaxed);
+		for reader in readers {
+			assert!(reader.join().unwrap() > 0);
+		}
+		assert_eq!(shared.get_message("hello", "en-US", None).unwrap(), "First");
+	}
+
+	#[test]
+	fn notifies_subscribers() {
+		let loc = version("First", false);
+		let before = loc.subscribe();
+		let unrelated = loc.clone().subscribe();
+		let shared = loc.into_shared();
+		let after = shared.subscribe();
+
+		shared.reload(version("Second", true));
+		for events in [&before, &after] {
+			assert_eq!(events.try_recv().unwrap().languages, ["en-US"]);
+		}
+
+		shared.update(|loc| loc.insert_from_reader("it", std::io::Cursor::new("hello = Ciao!"))).unwrap();
+		assert_eq!(after.try_recv().unwrap().languages, ["it"]);
+		assert!(shared.update(|loc| loc.remove_language("fr")).is_err());
+		shared.update(|_| Ok(())).unwrap();
+		assert!(after.try_recv().is_err());
+		assert!(unrelated.try_recv().is_err());
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn notifies_subscribers_of_reloads_from_disk() {
+		let root = std::env::temp_dir().join("fluent-fluently-shared-reload");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(&root).unwrap();
+		std::fs::write(root.join("en-US.ftl"), "hello = Hello!").unwrap();
+		std::fs::write(root.join("it.ftl"), "hello = Ciao!").unwrap();
+
+		let shared = version("First", false).into_shared();
+		let events = shared.subscribe();
+		shared.try_reload_from(&root, "en-US").unwrap();
+		assert_eq!(events.try_recv().unwrap().languages, ["en-US", "it"]);
+		assert!(shared.try_reload_from(root.join("missing"), "en-US").is_err());
+		assert!(events.try_recv().is_err());
+		assert_eq!(shared.get_message("hello", "it", None).unwrap(), "Ciao!");
+		std::fs::remove_dir_all(root).unwrap();
+	}
+}
@@ -0,0 +1,544 @@
+use std::path::{Path, PathBuf};
+use crate::{error::Result, manifest::Manifest};
+
+/// A language found by a [ResourceSource], along with the resources it is made of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageEntry {
+	/// The name the language was found under. It will only be loaded if it is a valid
+	/// language code, as defined by [LanguageIdentifier](unic_langid::LanguageIdentifier).
+	pub name: String,
+	/// Identifiers of the resources making up the language, to be passed to
+	/// [`ResourceSource::read()`].
+	pub resources: Vec<PathBuf>
+}
+
+/// Somewhere localisation files can be loaded from.
+/// `FileSystem` is the implementation used by [`Localiser::try_load()`](crate::Localiser::try_load),
+/// but anything else, such as an archive, a map embedded in the binary or a mock, may implement
+/// this and be loaded through [`Localiser::try_load_from()`](crate::Localiser::try_load_from).
+/// The source is kept by the [Localiser](crate::Localiser), to read languages that are loaded
+/// lazily.
+pub trait ResourceSource: Send + Sync {
+	/// Lists the languages available in this source. The resources of each language are loaded
+	/// in the order they are listed in, which matters when they define the same messages.
+	fn languages(&self) -> Result<Vec<LanguageEntry>>;
+
+	/// Reads the raw contents of one of the resources listed by [`Self::languages()`].
+	fn read(&self, resource: &Path) -> Result<Vec<u8>>;
+
+	/// Gives the namespace of one of the resources listed by [`Self::languages()`], made of
+	/// parts separated by `/`, each a valid Fluent identifier. Only used if namespaces were
+	/// enabled through
+	/// [`LocaliserBuilder::namespaced()`](crate::LocaliserBuilder::namespaced). By default,
+	/// resources have no namespace.
+	fn namespace(&self, resource: &Path) -> Option<String> {
+		let _ = resource;
+		None
+	}
+
+	/// Lists the entries that could not be turned into languages because their names can't be
+	/// represented as a [String], such as files whose names are not valid UTF-8. They are
+	/// reported by [`Localiser::load_failures()`](crate::Localiser::load_failures) rather than
+	/// silently left out. By default, there are none.
+	fn skipped(&self) -> Result<Vec<PathBuf>> {
+		Ok(Vec::new())
+	}
+
+	/// Lists the resources that exist but are left out on purpose, such as files filtered out by
+	/// patterns. They are reported by
+	/// [`Localiser::excluded_resources()`](crate::Localiser::excluded_resources). By default,
+	/// there are none.
+	fn excluded(&self) -> Result<Vec<PathBuf>> {
+		Ok(Vec::new())
+	}
+
+	/// Reads the [Manifest] declaring which languages to load, if there is one. By default,
+	/// there is none.
+	fn manifest(&self) -> Result<Option<Manifest>> {
+		Ok(None)
+	}
+}
+
+/// A [ResourceSource] keeping FTL sources in memory, for when they are embedded in the binary or
+/// obtained by other means.
+///
+/// ```rust
+/// use fluent_fluently::{InMemory, Localiser};
+///
+/// let source = InMemory::new()
+///     .with_resource("en-US", "hello = Hello!")
+///     .with_resource("it", "hello = Ciao!");
+/// let loc = Localiser::try_load_from(source, "en-US").unwrap();
+/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemory {
+	/// The languages and the sources of their resources, in insertion order.
+	languages: Vec<(String, Vec<String>)>,
+	/// The source of the manifest, if any.
+	manifest: Option<String>
+}
+
+impl InMemory {
+	/// Creates a new, empty instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a resource with the given FTL source to a language, creating it if needed.
+	pub fn with_resource(mut self, language: &str, source: impl Into<String>) -> Self {
+		match self.languages.iter_mut().find(|(lang, _)| lang == language) {
+			Some((_, sources)) => sources.push(source.into()),
+			None => self.languages.push((language.to_string(), vec![source.into()]))
+		}
+		self
+	}
+
+	/// Sets the source of the [Manifest] declaring which languages to load.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let source = InMemory::new()
+	///     .with_manifest("[en-US]\n[it]\nenabled = false\n[ca]\nfallback = [\"es\"]")
+	///     .with_resource("en-US", "hello = Hello!")
+	///     .with_resource("es", "hello = ¡Hola!")
+	///     .with_resource("it", "hello = Ciao!");
+	/// assert!(Localiser::try_load_from(source.clone(), "en-US").is_err());
+	///
+	/// let loc = Localiser::try_load_from(source.with_resource("ca", "bye = Adéu!"), "en-US").unwrap();
+	/// assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Hello!");
+	/// assert_eq!(loc.resolve_language("ca-ES"), "ca");
+	/// assert_eq!(loc.resolve_language("es"), "en-US");
+	/// ```
+	pub fn with_manifest(mut self, source: impl Into<String>) -> Self {
+		self.manifest = Some(source.into());
+		self
+	}
+}
+
+impl ResourceSource for InMemory {
+	fn languages(&self) -> Result<Vec<LanguageEntry>> {
+		Ok(self.languages.iter()
+			.map(|(language, sources)| LanguageEntry {
+				name: language.clone(),
+				resources: (0..sources.len())
+					.map(|i| Path::new(language).join(i.to_string()))
+					.collect()
+			}).collect())
+	}
+
+	fn read(&self, resource: &Path) -> Result<Vec<u8>> {
+		resource.parent()
+			.and_then(|language| language.to_str())
+			.zip(resource.file_name().and_then(|i| i.to_str()?.parse::<usize>().ok()))
+			.and_then(|(language, i)| {
+				self.languages.iter()
+					.find(|(lang, _)| lang == language)
+					.and_then(|(_, sources)| sources.get(i))
+			})
+			.map(|source| source.clone().into_bytes())
+			.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, format!("No such resource {}!", resource.display())).into())
+	}
+
+	fn manifest(&self) -> Result<Option<Manifest>> {
+		self.manifest.as_deref().map(Manifest::parse).transpose()
+	}
+}
+
+/// A [ResourceSource] reading from a directory on the filesystem.
+/// The directory's direct children are considered if they are either files with the `.ftl`
+/// extension or directories. In the first case, the file alone makes up the language; in the
+/// second case, all `.ftl` files in the directory and its subdirectories do. With the `gettext`
+/// feature, `.po` files are picked up as well, and converted as described in
+/// [`gettext::po_to_ftl()`](crate::gettext::po_to_ftl); the same goes for `.json` files and
+/// [`json::json_to_ftl()`](crate::json::json_to_ftl) with the `json-import` feature. Files and
+/// directories whose name starts with `.`, such as `.DS_Store`, `.git` or the `._` files macOS
+/// leaves around, are skipped unless [`Self::include_hidden()`] says otherwise. Files can also
+/// be picked through [`Self::include_glob()`] and [`Self::exclude_glob()`].
+///
+/// Each file is read with a single allocation of its exact size, which is then validated as
+/// UTF-8 and handed to the parser as it is, without being copied again. Files are not memory
+/// mapped: [FluentResource](fluent::FluentResource) needs to own its source anyway, so mapping
+/// would still copy everything once, and a file truncated while mapped would crash the process
+/// instead of failing to load.
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+	/// The directory languages are looked for in.
+	root: PathBuf,
+	/// Whether to consider files and directories whose name starts with `.`.
+	include_hidden: bool,
+	/// The patterns files must match one of to be considered, if any.
+	include_globs: Vec<String>,
+	/// The patterns files must not match any of to be considered.
+	exclude_globs: Vec<String>
+}
+
+#[cfg(feature = "fs")]
+impl FileSystem {
+	/// Creates a new instance reading from the given directory.
+	pub fn new(root: impl Into<PathBuf>) -> Self {
+		Self { root: root.into(), include_hidden: false, include_globs: Vec::new(), exclude_globs: Vec::new() }
+	}
+
+	/// Sets whether files and directories whose name starts with `.` are considered, which is
+	/// not the case by default.
+	///
+	/// ```rust
+	/// use fluent_fluently::{FileSystem, LocaliserBuilder};
+	///
+	/// let root = std::env::temp_dir().join("fluent-fluently-include-hidden");
+	/// std::fs::create_dir_all(root.join("en-US")).unwrap();
+	/// std::fs::write(root.join(".DS_Store"), [0, 0, 0, 1, 0xff]).unwrap();
+	/// std::fs::write(root.join("en-US").join("hello.ftl"), "hello = Hello!").unwrap();
+	/// std::fs::write(root.join("en-US").join("._hello.ftl"), [0, 5, 22, 7, 0xff]).unwrap();
+	///
+	/// let loc = LocaliserBuilder::with_source(FileSystem::new(&root), "en-US").build().unwrap();
+	/// assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hello!");
+	/// assert!(LocaliserBuilder::with_source(FileSystem::new(&root).include_hidden(true), "en-US").build().is_err());
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// ```
+	pub fn include_hidden(mut self, include: bool) -> Self {
+		self.include_hidden = include;
+		self
+	}
+
+	/// Only considers files whose path relative to the root matches the given pattern, or any of
+	/// the patterns given through other calls. Paths use `/` as separator on every platform.
+	/// In patterns, `?` matches any single character and `*` any sequence of characters, but
+	/// neither matches `/`; `**` as a whole segment matches any number of segments, including
+	/// none, so that `**/mt/**` matches both `mt/a.ftl` and `en-US/mt/a.ftl`. Patterns must
+	/// match the whole path: `*.ftl` only matches files at the root, and `**/*.ftl` matches them
+	/// anywhere. Languages made of a single file are matched by its name, such as `it.ftl`. A
+	/// language directory whose files are all left out is not loaded at all.
+	///
+	/// ```rust
+	/// use fluent_fluently::{FileSystem, LocaliserBuilder};
+	///
+	/// let root = std::env::temp_dir().join("fluent-fluently-include-glob");
+	/// std::fs::create_dir_all(root.join("en-US")).unwrap();
+	/// std::fs::write(root.join("en-US").join("menu.ftl"), "save = Save").unwrap();
+	/// std::fs::write(root.join("en-US").join("menu.draft.ftl"), "open = Open").unwrap();
+	///
+	/// let loc = LocaliserBuilder::with_source(FileSystem::new(&root).include_glob("**/*.draft.ftl"), "en-US").build().unwrap();
+	/// assert_eq!(loc.message_keys("en-US").unwrap(), ["open"]);
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// ```
+	pub fn include_glob(mut self, pattern: &str) -> Self {
+		self.include_globs.push(pattern.to_string());
+		self
+	}
+
+	/// Skips files whose path relative to the root matches the given pattern, or any of the
+	/// patterns given through other calls, even if they match those given to
+	/// [`Self::include_glob()`]. See there for the syntax. [`Self::excluded()`] lists the files
+	/// skipped this way.
+	///
+	/// ```rust
+	/// use fluent_fluently::{FileSystem, LocaliserBuilder};
+	///
+	/// let root = std::env::temp_dir().join("fluent-fluently-exclude-glob");
+	/// std::fs::create_dir_all(root.join("en-US").join("mt")).unwrap();
+	/// std::fs::write(root.join("en-US").join("menu.ftl"), "save = Save").unwrap();
+	/// std::fs::write(root.join("en-US").join("menu.draft.ftl"), "open = Open").unwrap();
+	/// std::fs::write(root.join("en-US").join("mt").join("menu.ftl"), "close = Close").unwrap();
+	///
+	/// let source = FileSystem::new(&root).exclude_glob("**/*.draft.ftl").exclude_glob("**/mt/**");
+	/// assert_eq!(source.excluded().unwrap().len(), 2);
+	/// let loc = LocaliserBuilder::with_source(source, "en-US").build().unwrap();
+	/// assert_eq!(loc.message_keys("en-US").unwrap(), ["save"]);
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// ```
+	pub fn exclude_glob(mut self, pattern: &str) -> Self {
+		self.exclude_globs.push(pattern.to_string());
+		self
+	}
+
+	/// Lists the resources that are left out because of the patterns given to
+	/// [`Self::include_glob()`] and [`Self::exclude_glob()`], sorted by path. Hidden files are
+	/// not included, whether or not they match.
+	pub fn excluded(&self) -> Result<Vec<PathBuf>> {
+		let mut excluded = self.candidates()?
+			.into_iter()
+			.flat_map(|path| self.walk(&path))
+			.filter(|path| !self.is_selected(path))
+			.collect::<Vec<_>>();
+		excluded.sort();
+		Ok(excluded)
+	}
+
+	/// Lists the direct children of the root that would hold a language, being directories or
+	/// resources, but are left out because their names are not valid UTF-8, and so can't be
+	/// language codes, sorted by path. Other files are not listed. Loading reports each of them in
+	/// [`Localiser::load_failures()`](crate::Localiser::load_failures). Files with such names inside the
+	/// directory of a language are loaded like any other, but can't be matched by the patterns
+	/// given to [`Self::include_glob()`] exactly, and have no namespace.
+	///
+	/// ```rust
+	/// # #[cfg(unix)] {
+	/// use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::PathBuf};
+	/// use fluent_fluently::{FileSystem, Localiser};
+	///
+	/// let root: PathBuf = std::env::temp_dir().join("fluent-fluently-skipped");
+	/// std::fs::create_dir_all(root.join("en-US")).unwrap();
+	/// std::fs::write(root.join("en-US").join(OsStr::from_bytes(b"caf\xe9.ftl")), "hello = Hello!").unwrap();
+	/// std::fs::write(root.join(OsStr::from_bytes(b"\xff.ftl")), "hello = ?").unwrap();
+	/// std::fs::write(root.join(OsStr::from_bytes(b"notes-\xff.txt")), "unrelated").unwrap();
+	///
+	/// let loc = Localiser::try_load(&root, "en-US").unwrap();
+	/// assert_eq!(loc.get_message("hello", "en-US", None).unwrap(), "Hello!");
+	/// assert_eq!(FileSystem::new(&root).skipped().unwrap(), [root.join(OsStr::from_bytes(b"\xff.ftl"))]);
+	/// assert_eq!(loc.load_failures()[0].path, root.join(OsStr::from_bytes(b"\xff.ftl")));
+	/// # std::fs::remove_dir_all(root).unwrap();
+	/// # }
+	/// ```
+	pub fn skipped(&self) -> Result<Vec<PathBuf>> {
+		let mut skipped = self.candidates()?
+			.into_iter()
+			.filter(|path| path.file_stem().is_some_and(|name| name.to_str().is_none()))
+			.filter(|path| path.is_dir() || Self::is_resource(path))
+			.collect::<Vec<_>>();
+		skipped.sort();
+		Ok(skipped)
+	}
+
+	/// Lists the direct children of the root that may hold a language, skipping hidden ones
+	/// unless requested.
+	fn candidates(&self) -> Result<Vec<PathBuf>> {
+		Ok(std::fs::read_dir(&self.root)
+			.map_err(|err| std::io::Error::new(err.kind(), format!("Failed to read {}: {}", self.root.display(), err)))?
+			.filter_map(|res| res.ok())
+			.map(|dir_entry| dir_entry.path())
+			.filter(|path| self.include_hidden || !Self::is_hidden(path))
+			.collect())
+	}
+
+	/// Checks whether a resource passes the patterns given to [`Self::include_glob()`] and
+	/// [`Self::exclude_glob()`].
+	fn is_selected(&self, path: &Path) -> bool {
+		if self.include_globs.is_empty() && self.exclude_globs.is_empty() {
+			return true;
+		}
+
+		let relative = path.strip_prefix(&self.root).unwrap_or(path)
+			.components()
+			.map(|part| part.as_os_str().to_string_lossy())
+			.collect::<Vec<_>>()
+			.join("/");
+
+		(self.include_globs.is_empty() || self.include_globs.iter().any(|glob| glob_matches(glob, &relative)))
+			&& !self.exclude_globs.iter().any(|glob| glob_matches(glob, &relative))
+	}
+
+	/// Finds all files in a certain folder and all of its subfolders that are resources, and
+	/// returns their paths in a [Vec], sorted by name. Patterns are not applied.
+	fn walk(&self, path: &Path) -> Vec<PathBuf> {
+		walkdir::WalkDir::new(path)
+			.follow_links(true)
+			.sort_by_file_name()
+			.into_iter()
+			.filter_entry(|entry| entry.depth() == 0 || self.include_hidden || !Self::is_hidden(entry.path()))
+			.filter_map(|e| e.ok())
+			.map(|entry| entry.into_path())
+			.filter(|entry_path| Self::is_resource(entry_path))
+			.collect()
+	}
+
+	/// Like [`Self::walk()`], but only keeps the resources that pass the patterns given to
+	/// [`Self::include_glob()`] and [`Self::exclude_glob()`].
+	fn path_to_resources(&self, path: &Path) -> Vec<PathBuf> {
+		self.walk(path).into_iter()
+			.filter(|entry_path| self.is_selected(entry_path))
+			.collect()
+	}
+
+	/// Checks whether a file or directory is hidden, going by whether its name starts with `.`.
+	fn is_hidden(path: &Path) -> bool {
+		path.file_name()
+			.is_some_and(|name| name.as_encoded_bytes().starts_with(b"."))
+	}
+
+	/// Checks whether a file is a resource, going by its extension: `.ftl`, `.po` with the
+	/// `gettext` feature or `.json` with the `json-import` feature.
+	fn is_resource(path: &Path) -> bool {
+		path.extension().is_some_and(|ext| ext == "ftl"
+			|| (cfg!(feature = "gettext") && ext == "po")
+			|| (cfg!(feature = "json-import") && ext == "json"))
+	}
+}
+
+#[cfg(feature = "fs")]
+impl ResourceSource for FileSystem {
+	fn languages(&self) -> Result<Vec<LanguageEntry>> {
+		let mut languages = self.candidates()?
+			.into_iter()
+			.filter_map(|path| {
+				let name = path.file_stem()?.to_str()?.to_string();
+				if path.is_dir() {
+					let resources = self.path_to_resources(&path);
+					// leave out languages whose files were all excluded, rather than load them empty
+					if resources.is_empty() && !self.walk(&path).is_empty() {
+						return None;
+					}
+					Some(LanguageEntry { name, resources })
+				} else if Self::is_resource(&path) && self.is_selected(&path) {
+					Some(LanguageEntry { name, resources: vec![path] })
+				} else {
+					None
+				}
+			}).collect::<Vec<_>>();
+		languages.sort_by(|a, b| a.name.cmp(&b.name));
+		Ok(languages)
+	}
+
+	fn read(&self, resource: &Path) -> Result<Vec<u8>> {
+		Ok(std::fs::read(resource)?)
+	}
+
+	fn skipped(&self) -> Result<Vec<PathBuf>> {
+		FileSystem::skipped(self)
+	}
+
+	fn excluded(&self) -> Result<Vec<PathBuf>> {
+		FileSystem::excluded(self)
+	}
+
+	/// The path of the resource within its language directory, without extension. Languages
+	/// made of a single file have no namespace.
+	fn namespace(&self, resource: &Path) -> Option<String> {
+		let parts = resource.strip_prefix(&self.root).ok()?
+			.with_extension("")
+			.components()
+			.skip(1)
+			.map(|part| part.as_os_str().to_str().map(str::to_string))
+			.collect::<Option<Vec<_>>>()?;
+		if parts.is_empty() {
+			return None;
+		}
+		Some(parts.join("/"))
+	}
+
+	/// Reads [`MANIFEST_NAME`](crate::manifest::MANIFEST_NAME) from the root, if it is there.
+	fn manifest(&self) -> Result<Option<Manifest>> {
+		let path = self.root.join(crate::manifest::MANIFEST_NAME);
+		if !path.is_file() {
+			return Ok(None);
+		}
+
+		let source = std::fs::read_to_string(&path)
+			.map_err(|err| std::io::Error::new(err.kind(), format!("Failed to read {}: {}", path.display(), err)))?;
+		Manifest::parse(&source).map(Some)
+	}
+}
+
+/// Checks whether a whole path matches a pattern as described in
+/// [`FileSystem::include_glob()`]. Segments are matched one by one, `**` standing for any
+/// number of them.
+#[cfg(feature = "fs")]
+fn glob_matches(glob: &str, path: &str) -> bool {
+	let glob = glob.split('/').collect::<Vec<_>>();
+	let path = path.split('/').collect::<Vec<_>>();
+	wildcard_matches(&glob, &path, |part| *part == "**", |part, segment| {
+		let part = part.chars().collect::<Vec<_>>();
+		let segment = segment.chars().collect::<Vec<_>>();
+		wildcard_matches(&part, &segment, |c| *c == '*', |c, other| *c == '?' || c == other)
+	})
+}
+
+/// Checks whether a whole sequence matches a pattern where some items stand for any number of
+/// items and the others for a single one, as `*` does in globs. Greedily tries the shortest
+/// match for the last of the former seen, so it takes at most quadratic time.
+#[cfg(feature = "fs")]
+fn wildcard_matches<P, T>(pattern: &[P], items: &[T], is_any: impl Fn(&P) -> bool, matches: impl Fn(&P, &T) -> bool) -> bool {
+	let (mut p, mut i) = (0, 0);
+	let mut last_any = None;
+	while i < items.len() {
+		if p < pattern.len() && is_any(&pattern[p]) {
+			last_any = Some((p, i));
+			p += 1;
+		} else if p < pattern.len() && matches(&pattern[p], &items[i]) {
+			p += 1;
+			i += 1;
+		} else if let Some((any, start)) = last_any {
+			// let the last wildcard take one more item, and try again from there
+			last_any = Some((any, start + 1));
+			p = any + 1;
+			i = start + 1;
+		} else {
+			return false;
+		}
+	}
+
+	pattern[p..].iter().all(is_any)
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(feature = "fs")]
+	#[test]
+	fn glob_matches_segments() {
+		use super::glob_matches;
+
+		assert!(glob_matches("*.ftl", "x.ftl"));
+		assert!(!glob_matches("*.ftl", "sub/x.ftl"));
+		assert!(glob_matches("*/*.ftl", "sub/x.ftl"));
+		assert!(glob_matches("**/*.ftl", "x.ftl"));
+		assert!(glob_matches("**/*.ftl", "sub/deeper/x.ftl"));
+		assert!(glob_matches("**/mt/**", "mt/a.ftl"));
+		assert!(glob_matches("**/mt/**", "en-US/mt/a.ftl"));
+		assert!(!glob_matches("**/mt/**", "en-US/mtx/a.ftl"));
+		assert!(glob_matches("en-??/**", "en-US/a/b.ftl"));
+		assert!(!glob_matches("en-?/*", "en-US/a.ftl"));
+		assert!(glob_matches("a/**/b", "a/b"));
+		assert!(glob_matches("a/**/b", "a/x/y/b"));
+		assert!(!glob_matches("a/**/b", "a/x/y/c"));
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn glob_matches_quickly() {
+		let path = format!("{}/{}", "a".repeat(200), "a".repeat(200));
+		assert!(!super::glob_matches("*a*a*a*a*a*a*a*a*a*a*b/*a*a*a*a*a*a*a*a*a*a*b", &path));
+		assert!(!super::glob_matches(&format!("{}b", "**/".repeat(30)), &"a/".repeat(200)));
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn fully_excluded_languages_are_skipped() {
+		use crate::{FileSystem, LocaliserBuilder};
+
+		let root = std::env::temp_dir().join("fluent-fluently-fully-excluded");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join("it")).unwrap();
+		std::fs::write(root.join("en-US.ftl"), "hello = Hello!").unwrap();
+		std::fs::write(root.join("it").join("menu.draft.ftl"), "hello = Ciao!").unwrap();
+
+		let loc = LocaliserBuilder::with_source(FileSystem::new(&root).exclude_glob("**/*.draft.ftl"), "en-US").build().unwrap();
+		assert_eq!(loc.available_languages().count(), 1);
+		std::fs::remove_dir_all(root).unwrap();
+	}
+
+	#[cfg(all(feature = "fs", unix))]
+	#[test]
+	fn only_language_names_are_skipped() {
+		use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+		use crate::{error::Error, FileSystem, Localiser};
+
+		let root = std::env::temp_dir().join("fluent-fluently-skipped-names");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join(OsStr::from_bytes(b"d\xe9"))).unwrap();
+		std::fs::write(root.join("en-US.ftl"), "hello = Hello!").unwrap();
+		std::fs::write(root.join(OsStr::from_bytes(b"\xff.ftl")), "hello = ?").unwrap();
+		std::fs::write(root.join(OsStr::from_bytes(b"notes-\xff.txt")), "unrelated").unwrap();
+
+		let skipped = FileSystem::new(&root).skipped().unwrap();
+		assert_eq!(skipped, [root.join(OsStr::from_bytes(b"d\xe9")), root.join(OsStr::from_bytes(b"\xff.ftl"))]);
+
+		let loc = Localiser::try_load(&root, "en-US").unwrap();
+		let failure = &loc.load_failures()[1];
+		assert!(matches!(&failure.error, Error::InvalidFileName { path } if *path == skipped[1]));
+		assert_eq!(failure.error.to_string(), format!("The name of {} is not valid UTF-8!", skipped[1].display()));
+		std::fs::remove_dir_all(root).unwrap();
+	}
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use fluent_syntax::ast;
+use crate::LoadedResource;
+
+/// A snapshot of how much content a [Localiser](crate::Localiser) holds.
+/// You can obtain a new instance by calling [`Localiser::stats()`](crate::Localiser::stats).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+	/// The number of loaded languages.
+	pub languages: usize,
+	/// A [HashMap] tying each language identifier to the number of messages it defines.
+	pub messages_per_language: HashMap<String, usize>,
+	/// The number of messages across all languages.
+	pub total_messages: usize,
+	/// A [HashMap] tying each language identifier to its [LanguageStats].
+	pub per_language: HashMap<String, LanguageStats>,
+	/// The sum of the [LanguageStats] of every language.
+	pub totals: LanguageStats
+}
+
+/// How much content a single language holds. Messages and terms defined more than once only
+/// count once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LanguageStats {
+	/// The number of messages.
+	pub messages: usize,
+	/// The number of terms.
+	pub terms: usize,
+	/// The number of attributes, across all messages and terms.
+	pub attributes: usize,
+	/// The number of resources the language was loaded from, usually files.
+	pub files: usize
+}
+
+impl LanguageStats {
+	/// Counts the content of the given resources, where later definitions replace earlier ones.
+	pub(crate) fn of(resources: &[LoadedResource]) -> Self {
+		let mut messages = HashMap::new();
+		let mut terms = HashMap::new();
+		for entry in resources.iter().flat_map(|res| res.resource.entries()) {
+			match entry {
+				ast::Entry::Message(msg) => { messages.insert(msg.id.name, msg.attributes.len()); },
+				ast::Entry::Term(term) => { terms.insert(term.id.name, term.attributes.len()); },
+				_ => {}
+			}
+		}
+
+		Self {
+			messages: messages.len(),
+			terms: terms.len(),
+			attributes: messages.values().chain(terms.values()).sum(),
+			files: resources.len()
+		}
+	}
+}
+
+impl std::ops::Add for LanguageStats {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		Self {
+			messages: self.messages + other.messages,
+			terms: self.terms + other.terms,
+			attributes: self.attributes + other.attributes,
+			files: self.files + other.files
+		}
+	}
+}
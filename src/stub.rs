@@ -0,0 +1,228 @@
+use std::io;
+use fluent_syntax::{ast, serializer};
+use crate::{error::Result, Localiser};
+
+/// What the stubs written by [`Localiser::write_missing_stubs_with()`] contain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StubMode {
+	/// Messages, terms and attributes are given an empty value, `{ "" }`.
+	#[default]
+	Empty,
+	/// The definition from the default language is copied as it is.
+	Copy
+}
+
+/// Serialises a single entry, without its comment.
+fn serialize_entry(entry: ast::Entry<&str>) -> String {
+	serializer::serialize(&ast::Resource { body: vec![entry] })
+}
+
+/// Creates a copy of an entry whose value and attributes are all empty.
+fn empty_entry<'s>(entry: &ast::Entry<&'s str>) -> Option<ast::Entry<&'s str>> {
+	let empty = || ast::Pattern {
+		elements: vec![ast::PatternElement::Placeable {
+			expression: ast::Expression::Inline(ast::InlineExpression::StringLiteral { value: "" })
+		}]
+	};
+	let empty_attributes = |attributes: &[ast::Attribute<&'s str>]| attributes.iter()
+		.map(|attr| ast::Attribute { id: attr.id.clone(), value: empty() })
+		.collect();
+
+	match entry {
+		ast::Entry::Message(msg) => Some(ast::Entry::Message(ast::Message {
+			id: msg.id.clone(),
+			value: msg.value.as_ref().map(|_| empty()),
+			attributes: empty_attributes(&msg.attributes),
+			comment: None
+		})),
+		ast::Entry::Term(term) => Some(ast::Entry::Term(ast::Term {
+			id: term.id.clone(),
+			value: empty(),
+			attributes: empty_attributes(&term.attributes),
+			comment: None
+		})),
+		_ => None
+	}
+}
+
+impl Localiser {
+	/// Writes an FTL stub for every message and term defined in the default language but not in
+	/// the given one, with empty values, and returns how many were written. See
+	/// [`Self::write_missing_stubs_with()`].
+	pub fn write_missing_stubs(&self, language: &str, writer: impl io::Write) -> Result<usize> {
+		self.write_missing_stubs_with(language, StubMode::Empty, writer)
+	}
+
+	/// Writes an FTL stub for every message and term defined in the default language but not in
+	/// the given one, and returns how many were written. Each stub is preceded by a comment
+	/// holding the definition from the default language, and keeps all of its attributes. The
+	/// output is valid FTL, ready to be filled in by translators and added to the language's
+	/// folder. The language does not need to be available: if it is not, everything is
+	/// considered missing.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser, stub::StubMode};
+	///
+	/// let source = InMemory::new()
+	///     .with_resource("en-US", "hello = Hello!\nbye = Bye, { $name }!\n    .title = Goodbye")
+	///     .with_resource("it", "hello = Ciao!");
+	/// let loc = Localiser::try_load_from(source, "en-US").unwrap();
+	///
+	/// let mut stubs = Vec::new();
+	/// assert_eq!(loc.write_missing_stubs_with("it", StubMode::Empty, &mut stubs).unwrap(), 1);
+	/// let stubs = String::from_utf8(stubs).unwrap();
+	/// assert_eq!(stubs, "\
+	/// ## bye = Bye, { $name }!
+	/// ##     .title = Goodbye
+	/// bye = { \"\" }
+	///     .title = { \"\" }
+	/// ");
+	/// assert!(fluent::FluentResource::try_new(stubs).is_ok());
+	/// ```
+	pub fn write_missing_stubs_with(&self, language: &str, mode: StubMode, mut writer: impl io::Write) -> Result<usize> {
+		let language = crate::fallback::parse_code(language)?.0;
+		let mut written = 0;
+		for entry in self.message_entries(self.default_language()).map(|msg| ast::Entry::Message(msg.clone()))
+			.chain(self.term_entries(self.default_language()).map(|term| ast::Entry::Term(term.clone())))
+		{
+			let entry = match entry {
+				ast::Entry::Message(msg) if self.message_entry(&language, msg.id.name).is_none() =>
+					ast::Entry::Message(ast::Message { comment: None, ..msg }),
+				ast::Entry::Term(term) if !self.term_entries(&language).any(|t| t.id.name == term.id.name) =>
+					ast::Entry::Term(ast::Term { comment: None, ..term }),
+				_ => continue
+			};
+
+			if written > 0 {
+				writeln!(writer)?;
+			}
+
+			let original = serialize_entry(entry.clone());
+			for line in original.lines() {
+				writeln!(writer, "# {}", line)?;
+			}
+
+			let stub = match mode {
+				StubMode::Empty => empty_entry(&entry).map(serialize_entry).unwrap_or_default(),
+				StubMode::Copy => original
+			};
+			write!(writer, "{}", stub)?;
+			written += 1;
+		}
+
+		Ok(written)
+	}
+
+	/// Like [`Self::write_missing_stubs_with()`], but does it for every available language other
+	/// than the default one, laid out inside the given directory the way
+	/// [FileSystem](crate::FileSystem) expects. If the directory has a `.ftl` file for the
+	/// language, such as `it.ftl`, the stubs are appended to it; otherwise they are written to
+	/// a file named `missing.ftl` in the folder of the language, which is created if needed.
+	/// Either way, passing the directory the [Localiser] was loaded from makes the stubs part of
+	/// their languages the next time it is loaded. Nothing is written for complete languages.
+	/// Returns how many stubs were written for each language.
+	#[cfg(feature = "fs")]
+	pub fn write_missing_stubs_to_dir(&self, dir: impl AsRef<std::path::Path>, mode: StubMode) -> Result<std::collections::HashMap<String, usize>> {
+		let mut languages = self.available_languages.keys()
+			.filter(|lang| *lang != self.default_language())
+			.collect::<Vec<_>>();
+		languages.sort();
+
+		let mut written = std::collections::HashMap::new();
+		for language in languages {
+			let mut stubs = Vec::new();
+			let count = self.write_missing_stubs_with(language, mode, &mut stubs)?;
+			if count == 0 {
+				continue;
+			}
+
+			match Self::language_file(dir.as_ref(), language)? {
+				Some(file) => {
+					let mut file = std::fs::OpenOptions::new().read(true).append(true).open(file)?;
+					if !Self::ends_with_newline(&mut file)? {
+						io::Write::write_all(&mut file, b"\n")?;
+					}
+					io::Write::write_all(&mut file, b"\n")?;
+					io::Write::write_all(&mut file, &stubs)?;
+				},
+				None => {
+					let folder = dir.as_ref().join(language);
+					std::fs::create_dir_all(&folder)?;
+					std::fs::write(folder.join("missing.ftl"), stubs)?;
+				}
+			}
+			written.insert(language.clone(), count);
+		}
+
+		Ok(written)
+	}
+
+	/// Finds the `.ftl` file making up the given language on its own in a directory, if there is
+	/// one and there is no folder for the language as well.
+	#[cfg(feature = "fs")]
+	fn language_file(dir: &std::path::Path, language: &str) -> io::Result<Option<std::path::PathBuf>> {
+		if !dir.is_dir() {
+			return Ok(None);
+		}
+
+		let mut file = None;
+		for entry in std::fs::read_dir(dir)? {
+			let path = entry?.path();
+			let is_language = |name: Option<&std::ffi::OsStr>| name.and_then(|name| name.to_str())
+				.is_some_and(|name| crate::fallback::parse_code(name).is_ok_and(|(code, _)| code == language));
+			if path.is_dir() && is_language(path.file_name()) {
+				return Ok(None);
+			}
+			if path.is_file() && path.extension().is_some_and(|ext| ext == "ftl") && is_language(path.file_stem()) {
+				file = Some(path);
+			}
+		}
+
+		Ok(file)
+	}
+
+	/// Checks whether a file is empty or ends with a line break.
+	#[cfg(feature = "fs")]
+	fn ends_with_newline(file: &mut std::fs::File) -> io::Result<bool> {
+		use io::{Read, Seek};
+		if file.seek(io::SeekFrom::End(0))? == 0 {
+			return Ok(true);
+		}
+
+		let mut last = [0];
+		file.seek(io::SeekFrom::End(-1))?;
+		file.read_exact(&mut last)?;
+		Ok(last[0] == b'\n')
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(feature = "fs")]
+	#[test]
+	fn stubs_round_trip() {
+		use crate::{stub::StubMode, Localiser};
+
+		let root = std::env::temp_dir().join("fluent-fluently-stubs-round-trip");
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join("fr")).unwrap();
+		std::fs::write(root.join("en-US.ftl"), "hello = Hello!\nbye = Bye!").unwrap();
+		std::fs::write(root.join("it.ftl"), "hello = Ciao!").unwrap();
+		std::fs::write(root.join("fr").join("main.ftl"), "hello = Bonjour !").unwrap();
+
+		let loc = Localiser::try_load(&root, "en-US").unwrap();
+		let written = loc.write_missing_stubs_to_dir(&root, StubMode::Copy).unwrap();
+		assert_eq!(written["it"], 1);
+		assert_eq!(written["fr"], 1);
+		assert!(!root.join("it").exists());
+		assert!(root.join("fr").join("missing.ftl").is_file());
+
+		let loc = Localiser::try_load(&root, "en-US").unwrap();
+		assert_eq!(loc.message_keys("it").unwrap(), ["hello", "bye"]);
+		assert_eq!(loc.get_message("hello", "it", None).unwrap(), "Ciao!");
+		assert_eq!(loc.get_message("bye", "it", None).unwrap(), "Bye!");
+		assert_eq!(loc.message_keys("fr").unwrap().len(), 2);
+		assert!(loc.write_missing_stubs_to_dir(&root, StubMode::Copy).unwrap().is_empty());
+		std::fs::remove_dir_all(root).unwrap();
+	}
+}
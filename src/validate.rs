@@ -0,0 +1,424 @@
+use std::{borrow::Cow, collections::{BTreeSet, HashMap}};
+use fluent_syntax::ast;
+use crate::Localiser;
+
+/// Something a pattern refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Reference<'s> {
+	/// A variable, which is expected to be passed as an argument.
+	Variable(&'s str),
+	/// Another message, or one of its attributes.
+	Message { id: &'s str, attribute: Option<&'s str> },
+	/// A term, or one of its attributes.
+	Term { id: &'s str, attribute: Option<&'s str> }
+}
+
+/// Collects everything a pattern refers to, in order of appearance. Every variant of select
+/// expressions is considered, as well as their selectors.
+pub(crate) fn pattern_references<'s>(pattern: &ast::Pattern<&'s str>) -> Vec<Reference<'s>> {
+	let mut refs = Vec::new();
+	collect_pattern(pattern, &mut refs);
+	refs
+}
+
+/// Collects the names of the variables used by a pattern.
+pub(crate) fn pattern_variables<'s>(pattern: &ast::Pattern<&'s str>) -> BTreeSet<&'s str> {
+	pattern_references(pattern).into_iter()
+		.filter_map(|r| match r {
+			Reference::Variable(name) => Some(name),
+			_ => None
+		}).collect()
+}
+
+/// Adds the references found in a pattern to the given [Vec].
+fn collect_pattern<'s>(pattern: &ast::Pattern<&'s str>, refs: &mut Vec<Reference<'s>>) {
+	for element in &pattern.elements {
+		if let ast::PatternElement::Placeable { expression } = element {
+			collect_expression(expression, refs);
+		}
+	}
+}
+
+/// Adds the references found in an expression to the given [Vec].
+fn collect_expression<'s>(expression: &ast::Expression<&'s str>, refs: &mut Vec<Reference<'s>>) {
+	match expression {
+		ast::Expression::Select { selector, variants } => {
+			collect_inline(selector, refs);
+			for variant in variants {
+				collect_pattern(&variant.value, refs);
+			}
+		},
+		ast::Expression::Inline(inline) => collect_inline(inline, refs)
+	}
+}
+
+/// Adds the references found in an inline expression to the given [Vec].
+fn collect_inline<'s>(inline: &ast::InlineExpression<&'s str>, refs: &mut Vec<Reference<'s>>) {
+	match inline {
+		ast::InlineExpression::StringLiteral { .. } | ast::InlineExpression::NumberLiteral { .. } => {},
+		ast::InlineExpression::FunctionReference { arguments, .. } => collect_arguments(arguments, refs),
+		ast::InlineExpression::MessageReference { id, attribute } => refs.push(Reference::Message {
+			id: id.name,
+			attribute: attribute.as_ref().map(|attr| attr.name)
+		}),
+		ast::InlineExpression::TermReference { id, attribute, arguments } => {
+			refs.push(Reference::Term {
+				id: id.name,
+				attribute: attribute.as_ref().map(|attr| attr.name)
+			});
+			if let Some(arguments) = arguments {
+				collect_arguments(arguments, refs);
+			}
+		},
+		ast::InlineExpression::VariableReference { id } => refs.push(Reference::Variable(id.name)),
+		ast::InlineExpression::Placeable { expression } => collect_expression(expression, refs)
+	}
+}
+
+/// Adds the references found in the arguments of a call to the given [Vec].
+fn collect_arguments<'s>(arguments: &ast::CallArguments<&'s str>, refs: &mut Vec<Reference<'s>>) {
+	for arg in &arguments.positional {
+		collect_inline(arg, refs);
+	}
+	for arg in &arguments.named {
+		collect_inline(&arg.value, refs);
+	}
+}
+
+/// A message whose translation uses different variables than the default language's.
+/// You can obtain these by calling [`Localiser::validate_placeables()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceableMismatch {
+	/// The language of the translation.
+	pub language: String,
+	/// The identifier of the message.
+	pub message: String,
+	/// The attribute the mismatch was found in, or [None] if it's in the message's value.
+	pub attribute: Option<String>,
+	/// Variables used in the default language but not in the translation.
+	pub missing: Vec<String>,
+	/// Variables used in the translation but not in the default language.
+	pub extra: Vec<String>
+}
+
+impl Localiser {
+	/// Compares the variables used by every message present in both the default language and
+	/// another one, attributes included, and reports those that don't match.
+	/// Selectors of select expressions count as used variables.
+	pub fn validate_placeables(&self) -> Vec<PlaceableMismatch> {
+		let mut mismatches = Vec::new();
+		let mut languages = self.available_languages.keys()
+			.filter(|lang| *lang != self.default_language())
+			.collect::<Vec<_>>();
+		languages.sort();
+
+		let default_messages = self.message_entries(self.default_language()).collect::<Vec<_>>();
+		for language in languages {
+			let messages = self.message_entries(language)
+				.map(|msg| (msg.id.name, msg))
+				.collect::<HashMap<_, _>>();
+			for default_msg in &default_messages {
+				let msg = match messages.get(default_msg.id.name) {
+					Some(msg) => msg,
+					None => continue
+				};
+
+				let mut compare = |attribute: Option<&str>, expected: &ast::Pattern<&str>, actual: &ast::Pattern<&str>| {
+					let expected = pattern_variables(expected);
+					let actual = pattern_variables(actual);
+					if expected != actual {
+						mismatches.push(PlaceableMismatch {
+							language: language.clone(),
+							message: self.public_key(msg.id.name),
+							attribute: attribute.map(str::to_string),
+							missing: expected.difference(&actual).map(|v| v.to_string()).collect(),
+							extra: actual.difference(&expected).map(|v| v.to_string()).collect()
+						});
+					}
+				};
+
+				if let (Some(expected), Some(actual)) = (&default_msg.value, &msg.value) {
+					compare(None, expected, actual);
+				}
+
+				for default_attr in &default_msg.attributes {
+					if let Some(attr) = msg.attributes.iter().find(|attr| attr.id.name == default_attr.id.name) {
+						compare(Some(attr.id.name), &default_attr.value, &attr.value);
+					}
+				}
+			}
+		}
+
+		mismatches
+	}
+}
+
+/// A reference to a message or term that is not defined in the same language.
+/// You can obtain these by calling [`Localiser::validate_references()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenReference {
+	/// The language the reference was found in.
+	pub language: String,
+	/// The identifier of the message or term containing the reference; terms are prefixed with
+	/// `-`, as they would be in FTL.
+	pub message: String,
+	/// The missing reference, written as it would be in FTL, such as `-brand-name` or `foo.bar`.
+	pub reference: String
+}
+
+impl Localiser {
+	/// Checks that every message and term reference, including references to attributes,
+	/// resolves to something defined in the same language. References may be satisfied by any
+	/// file belonging to the language.
+	pub fn validate_references(&self) -> Vec<BrokenReference> {
+		let mut broken = Vec::new();
+		let mut languages = self.available_languages.keys().collect::<Vec<_>>();
+		languages.sort();
+
+		for language in languages {
+			let messages = self.message_entries(language)
+				.map(|msg| (msg.id.name, &msg.attributes))
+				.collect::<HashMap<_, _>>();
+			let terms = self.term_entries(language)
+				.map(|term| (term.id.name, &term.attributes))
+				.collect::<HashMap<_, _>>();
+
+			let resolves = |defined: &HashMap<&str, &Vec<ast::Attribute<&str>>>, id: &str, attribute: Option<&str>| {
+				defined.get(id).is_some_and(|attrs| match attribute {
+					Some(attribute) => attrs.iter().any(|attr| attr.id.name == attribute),
+					None => true
+				})
+			};
+
+			let mut check = |name: String, pattern: &ast::Pattern<&str>| {
+				for reference in pattern_references(pattern) {
+					let (ok, prefix, id, attribute) = match reference {
+						Reference::Variable(_) => continue,
+						Reference::Message { id, attribute } => (resolves(&messages, id, attribute), "", Cow::Owned(self.public_key(id)), attribute),
+						Reference::Term { id, attribute } => (resolves(&terms, id, attribute), "-", Cow::Borrowed(id), attribute)
+					};
+
+					if !ok {
+						broken.push(BrokenReference {
+							language: language.clone(),
+							message: name.clone(),
+							reference: match attribute {
+								Some(attribute) => format!("{}{}.{}", prefix, id, attribute),
+								None => format!("{}{}", prefix, id)
+							}
+						});
+					}
+				}
+			};
+
+			for entry in self.entries(language) {
+				let (name, value, attributes) = match entry {
+					ast::Entry::Message(msg) => (self.public_key(msg.id.name), msg.value.as_ref(), &msg.attributes),
+					ast::Entry::Term(term) => (format!("-{}", term.id.name), Some(&term.value), &term.attributes),
+					_ => continue
+				};
+
+				if let Some(value) = value {
+					check(name.clone(), value);
+				}
+				for attr in attributes {
+					check(name.clone(), &attr.value);
+				}
+			}
+		}
+
+		broken
+	}
+}
+
+/// A select expression listing the same variant key more than once.
+/// You can obtain these by calling [`Localiser::validate_selectors()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorIssue {
+	/// The language the select expression was found in.
+	pub language: String,
+	/// The identifier of the message or term containing it; terms are prefixed with `-`, as
+	/// they would be in FTL.
+	pub message_id: String,
+	/// The repeated key, as written in its second occurrence.
+	pub key: String
+}
+
+/// Collects the variant keys repeated within a select expression of a pattern, looking into the
+/// variants of select expressions and into nested placeables. Numeric keys are compared by value,
+/// as Fluent matches them, so `[1]` and `[1.0]` are the same key.
+fn repeated_keys<'s>(pattern: &ast::Pattern<&'s str>, repeated: &mut Vec<&'s str>) {
+	fn check_expression<'s>(expression: &ast::Expression<&'s str>, repeated: &mut Vec<&'s str>) {
+		match expression {
+			ast::Expression::Select { selector, variants } => {
+				check_inline(selector, repeated);
+				let mut seen = Vec::new();
+				for variant in variants {
+					let key = match variant.key {
+						ast::VariantKey::Identifier { name } => (name, None),
+						ast::VariantKey::NumberLiteral { value } => (value, value.parse::<f64>().ok())
+					};
+					let same = |(name, number): &(&str, Option<f64>)| match (number, key.1) {
+						(Some(a), Some(b)) => *a == b,
+						_ => *name == key.0
+					};
+					if seen.iter().any(same) {
+						repeated.push(key.0);
+					} else {
+						seen.push(key);
+					}
+					repeated_keys(&variant.value, repeated);
+				}
+			},
+			ast::Expression::Inline(inline) => check_inline(inline, repeated)
+		}
+	}
+
+	fn check_inline<'s>(inline: &ast::InlineExpression<&'s str>, repeated: &mut Vec<&'s str>) {
+		if let ast::InlineExpression::Placeable { expression } = inline {
+			check_expression(expression, repeated);
+		}
+	}
+
+	for element in &pattern.elements {
+		if let ast::PatternElement::Placeable { expression } = element {
+			check_expression(expression, repeated);
+		}
+	}
+}
+
+impl Localiser {
+	/// Finds the select expressions, in messages, terms and their attributes, listing the same
+	/// variant key more than once. Fluent picks the first variant matching the selector, so the
+	/// others can never be shown: usually a typo for another key. The parser accepts them, and
+	/// so does the bundle.
+	///
+	/// ```rust
+	/// use fluent_fluently::{InMemory, Localiser};
+	///
+	/// let loc = Localiser::try_load_from(InMemory::new()
+	///     .with_resource("en-US", "files = { $count ->\n    [one] One file\n    [one] Two files\n   *[other] { $count } files\n}"), "en-US").unwrap();
+	/// let issues = loc.validate_selectors();
+	/// assert_eq!(issues.len(), 1);
+	/// assert_eq!((issues[0].message_id.as_str(), issues[0].key.as_str()), ("files", "one"));
+	/// ```
+	pub fn validate_selectors(&self) -> Vec<SelectorIssue> {
+		let mut issues = Vec::new();
+		let mut languages = self.available_languages.keys().collect::<Vec<_>>();
+		languages.sort();
+
+		for language in languages {
+			for entry in self.entries(language) {
+				let (name, value, attributes) = match entry {
+					ast::Entry::Message(msg) => (self.public_key(msg.id.name), msg.value.as_ref(), &msg.attributes),
+					ast::Entry::Term(term) => (format!("-{}", term.id.name), Some(&term.value), &term.attributes),
+					_ => continue
+				};
+
+				let mut repeated = Vec::new();
+				for pattern in value.into_iter().chain(attributes.iter().map(|attr| &attr.value)) {
+					repeated_keys(pattern, &mut repeated);
+				}
+				issues.extend(repeated.into_iter().map(|key| SelectorIssue {
+					language: language.clone(),
+					message_id: name.clone(),
+					key: key.to_string()
+				}));
+			}
+		}
+
+		issues
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{InMemory, Localiser};
+	use super::{PlaceableMismatch, SelectorIssue};
+
+	fn mismatch(message: &str, attribute: Option<&str>, missing: &[&str], extra: &[&str]) -> PlaceableMismatch {
+		PlaceableMismatch {
+			language: "de".to_string(),
+			message: message.to_string(),
+			attribute: attribute.map(str::to_string),
+			missing: missing.iter().map(|v| v.to_string()).collect(),
+			extra: extra.iter().map(|v| v.to_string()).collect()
+		}
+	}
+
+	#[test]
+	fn validates_placeables() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", r#"
+same = { $user } has { NUMBER($count, minimumFractionDigits: 2) } files
+renamed = You have { $count } files
+nested = { { $user } } said { "hi" }
+variants = { $count ->
+    [one] One file in { $folder }
+   *[other] { $count } files
+}
+selector = { $gender ->
+   *[other] Their files
+}
+login = Log in
+    .title = Log in as { $user }
+only-default = { $user }
+"#)
+			.with_resource("de", r#"
+same = { $user } hat { NUMBER($count, minimumFractionDigits: 2) } Dateien
+renamed = Sie haben { $anzahl } Dateien
+nested = { $user } sagte { { $greeting } }
+variants = { $count ->
+    [one] Eine Datei
+   *[other] { $count } Dateien in { $folder }
+}
+selector = Ihre Dateien
+login = Anmelden als { $user }
+    .title = Anmelden
+"#), "en-US").unwrap();
+
+		assert_eq!(loc.validate_placeables(), [
+			mismatch("renamed", None, &["count"], &["anzahl"]),
+			mismatch("nested", None, &[], &["greeting"]),
+			mismatch("selector", None, &["gender"], &[]),
+			mismatch("login", None, &[], &["user"]),
+			mismatch("login", Some("title"), &["user"], &[])
+		]);
+	}
+
+	#[test]
+	fn finds_repeated_variants() {
+		let loc = Localiser::try_load_from(InMemory::new()
+			.with_resource("en-US", r#"
+files = { $count ->
+    [one] One file
+    [one] Two files
+   *[other] { $count } files
+}
+-brand = { $case ->
+   *[nominative] Firefox
+    [1] One
+    [1.0] Also one
+}
+login = Log in
+    .title = { $gender ->
+        [female] { $count ->
+            [other] Nested
+           *[other] Nested again
+        }
+       *[other] Fine
+    }
+fine = { $count ->
+    [one] One
+    [1] Also fine
+   *[other] Other
+}
+"#), "en-US").unwrap();
+		let issue = |message_id: &str, key: &str| SelectorIssue {
+			language: "en-US".to_string(),
+			message_id: message_id.to_string(),
+			key: key.to_string()
+		};
+		assert_eq!(loc.validate_selectors(), [issue("files", "one"), issue("-brand", "1.0"), issue("login", "other")]);
+	}
+}
@@ -0,0 +1,46 @@
+//! Exercises the code generated by `#[derive(ToFluentArgs)]`.
+#![cfg(feature = "derive")]
+
+use fluent_fluently::{error::Error, InMemory, Localiser, ToFluentArgs};
+
+#[derive(ToFluentArgs)]
+struct Inbox {
+	name: String,
+	#[fluent(rename = "count")]
+	unread: u32,
+	#[fluent(skip)]
+	owner_id: u64,
+	folder: Option<String>
+}
+
+fn localiser() -> Localiser {
+	let source = InMemory::new()
+		.with_resource("en-US", include_str!("locale/en-US/inbox.ftl"));
+	Localiser::try_load_from(source, "en-US").unwrap()
+}
+
+#[test]
+fn renamed_fields_drive_selectors() {
+	let loc = localiser();
+	let mut inbox = Inbox { name: "Ada".to_string(), unread: 0, owner_id: 7, folder: None };
+	assert_eq!(loc.get_message("inbox", "en-US", Some(&inbox.to_args())).unwrap(), "\u{2068}Ada\u{2069}, you have no new messages.");
+	inbox.unread = 1;
+	assert_eq!(loc.get_message("inbox", "en-US", Some(&inbox.to_args())).unwrap(), "\u{2068}Ada\u{2069}, you have one new message.");
+	inbox.unread = 5;
+	assert_eq!(loc.get_message("inbox", "en-US", Some(&inbox.to_args())).unwrap(), "\u{2068}Ada\u{2069}, you have \u{2068}5\u{2069} new messages.");
+}
+
+#[test]
+fn skipped_and_missing_fields_are_left_out() {
+	let loc = localiser();
+	let mut inbox = Inbox { name: "Ada".to_string(), unread: 2, owner_id: 7, folder: None };
+	let args = inbox.to_args();
+	assert!(args.get("unread").is_none());
+	assert!(args.get("owner_id").is_none());
+	assert!(args.get("folder").is_none());
+	assert_eq!(inbox.owner_id, 7);
+	assert!(matches!(loc.get_message("folder", "en-US", Some(&args)), Err(Error::FormatErrors(_))));
+
+	inbox.folder = Some("Work".to_string());
+	assert_eq!(loc.get_message("folder", "en-US", Some(&inbox.to_args())).unwrap(), "Folder: \u{2068}Work\u{2069}");
+}
@@ -0,0 +1,7 @@
+hello = Hello!
+save = Save
+files =
+    { $count ->
+        [one] One file
+       *[other] { $count } files
+    }
@@ -0,0 +1,2 @@
+hello = Ciao!
+save = Salva
@@ -0,0 +1,6 @@
+inbox = { $count ->
+    [0] { $name }, you have no new messages.
+    [one] { $name }, you have one new message.
+   *[other] { $name }, you have { $count } new messages.
+}
+folder = Folder: { $folder }
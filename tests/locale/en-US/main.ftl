@@ -0,0 +1,3 @@
+hello-world = Hello, world!
+login =
+    .title = Log in
@@ -0,0 +1 @@
+hello-world = Ciao, mondo!
@@ -0,0 +1,26 @@
+//! Exercises the constants generated by `messages!`.
+#![cfg(all(feature = "derive", feature = "fs"))]
+
+use fluent_fluently::{fluent_args, Localiser};
+
+fluent_fluently::messages!("tests/locale", "en-US");
+
+fn localiser() -> Localiser {
+	Localiser::try_load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/locale"), "en-US").unwrap()
+}
+
+#[test]
+fn constants_hold_the_keys() {
+	assert_eq!(messages::HELLO_WORLD.as_str(), "hello-world");
+	assert_eq!(messages::INBOX.as_str(), "inbox");
+	assert_eq!(messages::FOLDER.as_str(), "folder");
+}
+
+#[test]
+fn constants_get_messages() {
+	let loc = localiser();
+	assert_eq!(messages::HELLO_WORLD.get(&loc, "it", None).unwrap(), "Ciao, mondo!");
+	assert_eq!(messages::HELLO_WORLD.get(&loc, "fr", None).unwrap(), "Hello, world!");
+	let args = fluent_args!{ folder => "Work" };
+	assert_eq!(messages::FOLDER.get(&loc, "en-US", Some(&args)).unwrap(), "Folder: \u{2068}Work\u{2069}");
+}